@@ -0,0 +1,68 @@
+//! Scans synthetic Scrawl files of growing sizes and reports the throughput.
+//! With a linear scanner, the time per byte stays roughly the same for every size.
+//!
+//! Run with `cargo bench --bench scanner`.
+
+use std::time::{Duration, Instant};
+
+const SAMPLE: &str = "import IO;
+
+print(text: String) {
+    file = new IO::File(\"stdout\");
+    file.write(text);
+
+    file.close();
+}
+
+total = (count + 42) * 7 >>= 1;
+print(\"Hello, world!\");
+";
+
+const SIZES: [usize; 4] = [128 * 1024, 256 * 1024, 512 * 1024, 1024 * 1024];
+const RUNS: u32 = 5;
+
+fn synthesize(size: usize) -> String {
+    let mut content = String::with_capacity(size + SAMPLE.len());
+
+    while content.len() < size {
+        content.push_str(SAMPLE);
+    }
+
+    content
+}
+
+fn measure(content: &str) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut count = 0;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let mut scanner = scrawlc::Scanner::new(content).unwrap();
+        let tokens = scanner.scan().unwrap();
+
+        best = best.min(start.elapsed());
+        count = tokens.len();
+    }
+
+    (best, count)
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>10} {:>12} {:>10}",
+        "bytes", "tokens", "time", "ns/byte"
+    );
+
+    for size in SIZES {
+        let content = synthesize(size);
+        let (time, count) = measure(&content);
+
+        println!(
+            "{:>10} {:>10} {:>12.2?} {:>10.2}",
+            content.len(),
+            count,
+            time,
+            time.as_nanos() as f64 / content.len() as f64
+        );
+    }
+}
@@ -1,3 +1,7 @@
 mod scanner;
 
-pub use scanner::{Position, Scanner, ScannerError, Token, ETX, LF};
+pub use scanner::{
+    is_identifier_continue, is_identifier_start, Literal, NumberSuffix, Position, Scanner,
+    ScannerError, ScannerWarning, Span, StringStyle, Token, TokenKind, Tokens, Trivia, TriviaKind,
+    KEYWORDS, LF, LOOKALIKES, OPERATORS,
+};
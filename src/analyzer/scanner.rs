@@ -1,26 +1,385 @@
 mod position;
+mod span;
 mod token;
+mod trivia;
 
 pub use position::Position;
-pub use token::Token;
+pub use span::Span;
+pub use token::{Literal, NumberSuffix, StringStyle, Token, TokenKind, KEYWORDS};
+pub use trivia::{Trivia, TriviaKind};
 
+use crate::{
+    diagnostic::{Applicability, Diagnostic, Suggestion},
+    lint::{Lint, CONFUSABLE_IDENTIFIERS, MIXED_SCRIPT_IDENTIFIERS},
+    source_map::FileId,
+};
+
+use std::collections::HashMap;
 use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::{confusable_detection::skeleton, MixedScript};
+use unicode_xid::UnicodeXID;
 
-pub const ETX: char = 0x03 as char; // End Of Text
+pub const NUL: char = 0x00 as char; // Null, the current character once the content is consumed
 pub const TAB: char = 0x09 as char; // Tab ('\t')
 pub const LF: char = 0x0A as char; // Line Feed ('\n')
 pub const CR: char = 0x0D as char; // Carriage Return ('\r')
+pub const BOM: char = '\u{FEFF}'; // Byte Order Mark
 
-pub const IDENTIFIER_SET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_";
 pub const NUMBER_SET: &str = "0123456789";
 
-#[derive(Debug, Error)]
+/// Unicode characters which look like ASCII punctuation, with their names and the ASCII character
+/// meant, which an unsupported character error suggests instead.
+pub const LOOKALIKES: &[(char, &str, char, &str)] = &[
+    ('“', "Left Double Quotation Mark", '"', "Quotation Mark"),
+    ('”', "Right Double Quotation Mark", '"', "Quotation Mark"),
+    ('„', "Double Low-9 Quotation Mark", '"', "Quotation Mark"),
+    (
+        '‟',
+        "Double High-Reversed-9 Quotation Mark",
+        '"',
+        "Quotation Mark",
+    ),
+    ('＂', "Fullwidth Quotation Mark", '"', "Quotation Mark"),
+    ('‘', "Left Single Quotation Mark", '\'', "Apostrophe"),
+    ('’', "Right Single Quotation Mark", '\'', "Apostrophe"),
+    (
+        '‛',
+        "Single High-Reversed-9 Quotation Mark",
+        '\'',
+        "Apostrophe",
+    ),
+    ('＇', "Fullwidth Apostrophe", '\'', "Apostrophe"),
+    ('\u{37E}', "Greek Question Mark", ';', "Semicolon"),
+    ('；', "Fullwidth Semicolon", ';', "Semicolon"),
+    ('，', "Fullwidth Comma", ',', "Comma"),
+    ('（', "Fullwidth Left Parenthesis", '(', "Left Parenthesis"),
+    (
+        '）',
+        "Fullwidth Right Parenthesis",
+        ')',
+        "Right Parenthesis",
+    ),
+    ('−', "Minus Sign", '-', "Hyphen-Minus"),
+    ('\u{A0}', "No-Break Space", ' ', "Space"),
+];
+
+/// Punctuators and operators, with the source text they are scanned from.
+/// The longest text matching the content wins, so `>>=` is one token rather than `>>` and `=`.
+/// Braces are not listed, as they also open and close interpolations in strings.
+pub const OPERATORS: &[(&str, TokenKind)] = &[
+    ("(", TokenKind::LeftParen),
+    (")", TokenKind::RightParen),
+    ("[", TokenKind::LeftBracket),
+    ("]", TokenKind::RightBracket),
+    (";", TokenKind::Semicolon),
+    (",", TokenKind::Comma),
+    (".", TokenKind::Dot),
+    ("..", TokenKind::DotDot),
+    ("...", TokenKind::DotDotDot),
+    ("..=", TokenKind::DotDotEqual),
+    ("@", TokenKind::At),
+    ("#", TokenKind::Hash),
+    ("=", TokenKind::Equal),
+    ("==", TokenKind::EqualEqual),
+    ("=>", TokenKind::FatArrow),
+    ("+", TokenKind::Plus),
+    ("+=", TokenKind::PlusEqual),
+    ("++", TokenKind::PlusPlus),
+    (">", TokenKind::Greater),
+    (">=", TokenKind::GreaterEqual),
+    (">>", TokenKind::GreaterGreater),
+    (">>=", TokenKind::GreaterGreaterEqual),
+    ("-", TokenKind::Minus),
+    ("-=", TokenKind::MinusEqual),
+    ("--", TokenKind::MinusMinus),
+    ("->", TokenKind::Arrow),
+    ("<", TokenKind::Less),
+    ("<=", TokenKind::LessEqual),
+    ("<<", TokenKind::LessLess),
+    ("<<=", TokenKind::LessLessEqual),
+    ("*", TokenKind::Star),
+    ("*=", TokenKind::StarEqual),
+    ("**", TokenKind::StarStar),
+    ("**=", TokenKind::StarStarEqual),
+    ("!", TokenKind::Bang),
+    ("!=", TokenKind::BangEqual),
+    ("/", TokenKind::Slash),
+    ("/=", TokenKind::SlashEqual),
+    ("~", TokenKind::Tilde),
+    ("&", TokenKind::Ampersand),
+    ("&=", TokenKind::AmpersandEqual),
+    ("&&", TokenKind::AmpersandAmpersand),
+    ("&&=", TokenKind::AmpersandAmpersandEqual),
+    ("?", TokenKind::Question),
+    ("??", TokenKind::QuestionQuestion),
+    ("??=", TokenKind::QuestionQuestionEqual),
+    ("?.", TokenKind::QuestionDot),
+    ("|", TokenKind::Pipe),
+    ("|=", TokenKind::PipeEqual),
+    ("||", TokenKind::PipePipe),
+    ("||=", TokenKind::PipePipeEqual),
+    (":", TokenKind::Colon),
+    ("::", TokenKind::ColonColon),
+    ("^", TokenKind::Caret),
+    ("^=", TokenKind::CaretEqual),
+    ("%", TokenKind::Percent),
+    ("%=", TokenKind::PercentEqual),
+];
+
+/// Returns `true` if the character can start an identifier.
+/// Identifiers follow UAX #31: they start with an `XID_Start` character or `_`.
+///
+/// # Examples
+/// ```
+/// assert!(scrawlc::is_identifier_start('_'));
+/// assert!(scrawlc::is_identifier_start('é'));
+/// assert!(scrawlc::is_identifier_start('변'));
+/// assert!(!scrawlc::is_identifier_start('1'));
+/// ```
+pub fn is_identifier_start(character: char) -> bool {
+    character == '_' || character.is_xid_start()
+}
+
+/// Returns `true` if the character can continue an identifier, i.e. is an `XID_Continue` character.
+///
+/// # Examples
+/// ```
+/// assert!(scrawlc::is_identifier_continue('1'));
+/// assert!(scrawlc::is_identifier_continue('\u{301}'));
+/// assert!(!scrawlc::is_identifier_continue('-'));
+/// ```
+pub fn is_identifier_continue(character: char) -> bool {
+    character.is_xid_continue()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum ScannerError {
-    #[error("cannot access {0}, end of content")]
-    EndOfContent(Position),
+    #[error("cannot access {}, end of content", .0.start)]
+    EndOfContent(Span),
 
     #[error("{0} is an unsupported character")]
-    UnsupportedCharacter(char),
+    UnsupportedCharacter(char, Span),
+
+    #[error("unterminated string literal")]
+    UnterminatedString(Span),
+
+    #[error("unterminated character literal")]
+    UnterminatedCharacter(Span),
+
+    #[error("\\{} is an invalid escape", .0.escape_debug())]
+    InvalidEscape(char, Span),
+
+    #[error("empty character literal")]
+    EmptyCharacter(Span),
+
+    #[error("character literal may only contain one character")]
+    OverlongCharacter(Span),
+
+    #[error("malformed number literal")]
+    MalformedNumber(Span),
+
+    #[error("number literal is out of range for its type")]
+    NumberOverflow(Span),
+
+    #[error("unterminated block comment")]
+    UnterminatedBlockComment(Span),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ScannerWarning {
+    #[error("identifier `{0}` mixes characters from different scripts")]
+    MixedScriptIdentifier(String, Span),
+
+    #[error("identifier `{0}` is confusable with `{1}`")]
+    ConfusableIdentifier(String, String, Span, Span),
+}
+
+impl ScannerWarning {
+    /// Returns the span of the content the warning refers to.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("let pаy = 1;").unwrap();
+    /// scanner.scan().unwrap();
+    ///
+    /// assert_eq!(scanner.warnings()[0].span().slice(scanner.content()), "pаy");
+    /// ```
+    pub fn span(&self) -> &Span {
+        match self {
+            ScannerWarning::MixedScriptIdentifier(_, span) => span,
+            ScannerWarning::ConfusableIdentifier(_, _, span, _) => span,
+        }
+    }
+
+    /// Returns the stable code of the warning, which `scrawlc --explain` explains.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("let pаy = 1;").unwrap();
+    /// scanner.scan().unwrap();
+    ///
+    /// assert_eq!(scanner.warnings()[0].code(), "W0001");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            ScannerWarning::MixedScriptIdentifier(..) => "W0001",
+            ScannerWarning::ConfusableIdentifier(..) => "W0002",
+        }
+    }
+
+    /// Returns the lint reporting the warning, which sets the level it is reported at.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("let pаy = 1;").unwrap();
+    /// scanner.scan().unwrap();
+    ///
+    /// assert_eq!(scanner.warnings()[0].lint().name, "mixed_script_identifiers");
+    /// ```
+    pub fn lint(&self) -> &'static Lint {
+        match self {
+            ScannerWarning::MixedScriptIdentifier(..) => &MIXED_SCRIPT_IDENTIFIERS,
+            ScannerWarning::ConfusableIdentifier(..) => &CONFUSABLE_IDENTIFIERS,
+        }
+    }
+
+    /// Returns the diagnostic reporting the warning.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("let ѕсоре = 1; let scope = 2;").unwrap();
+    /// scanner.scan().unwrap();
+    ///
+    /// let diagnostic = scanner.warnings()[0].diagnostic();
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Warning);
+    /// assert_eq!(diagnostic.labels()[0].span().slice(scanner.content()), "ѕсоре");
+    /// ```
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::warning(&self.to_string(), self.span()).with_code(self.code());
+
+        match self {
+            ScannerWarning::MixedScriptIdentifier(..) => diagnostic
+                .with_primary_label("mixes scripts")
+                .with_note("characters from different scripts can look alike"),
+            ScannerWarning::ConfusableIdentifier(_, other, _, other_span) => diagnostic
+                .with_primary_label("this identifier")
+                .with_label(other_span, &format!("looks like `{}` here", other)),
+        }
+    }
+}
+
+impl ScannerError {
+    /// Returns the span of the content the error refers to.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a = $;").unwrap();
+    /// let error = scanner.scan().unwrap_err();
+    ///
+    /// assert_eq!(error.span().slice(scanner.content()), "$");
+    /// assert_eq!(error.span().start.to_string(), "1:5");
+    /// ```
+    pub fn span(&self) -> &Span {
+        match self {
+            ScannerError::EndOfContent(span) => span,
+            ScannerError::UnsupportedCharacter(_, span) => span,
+            ScannerError::UnterminatedString(span) => span,
+            ScannerError::UnterminatedCharacter(span) => span,
+            ScannerError::InvalidEscape(_, span) => span,
+            ScannerError::EmptyCharacter(span) => span,
+            ScannerError::OverlongCharacter(span) => span,
+            ScannerError::MalformedNumber(span) => span,
+            ScannerError::NumberOverflow(span) => span,
+            ScannerError::UnterminatedBlockComment(span) => span,
+        }
+    }
+
+    /// Returns the stable code of the error, which `scrawlc --explain` explains.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a = $;").unwrap();
+    ///
+    /// assert_eq!(scanner.scan().unwrap_err().code(), "E0002");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            ScannerError::EndOfContent(_) => "E0001",
+            ScannerError::UnsupportedCharacter(..) => "E0002",
+            ScannerError::UnterminatedString(_) => "E0003",
+            ScannerError::UnterminatedCharacter(_) => "E0004",
+            ScannerError::InvalidEscape(..) => "E0005",
+            ScannerError::EmptyCharacter(_) => "E0006",
+            ScannerError::OverlongCharacter(_) => "E0007",
+            ScannerError::MalformedNumber(_) => "E0008",
+            ScannerError::NumberOverflow(_) => "E0009",
+            ScannerError::UnterminatedBlockComment(_) => "E0010",
+        }
+    }
+
+    /// Returns the diagnostic reporting the error.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("'ab'").unwrap();
+    /// let diagnostic = scanner.scan().unwrap_err().diagnostic();
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Error);
+    /// assert_eq!(diagnostic.message(), "character literal may only contain one character");
+    /// assert!(diagnostic.help().is_some());
+    /// ```
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(&self.to_string(), self.span()).with_code(self.code());
+
+        match self {
+            ScannerError::EndOfContent(_) => diagnostic.with_primary_label("end of content"),
+            ScannerError::UnsupportedCharacter(character, span) => {
+                let diagnostic = diagnostic.with_primary_label("unsupported character");
+
+                match LOOKALIKES.iter().find(|(lookalike, ..)| lookalike == character) {
+                    Some((_, name, ascii, ascii_name)) => diagnostic.with_suggestion(Suggestion::new(
+                        &format!("replace the {} with an ASCII {}", name, ascii_name),
+                        span,
+                        &ascii.to_string(),
+                        Applicability::MachineApplicable,
+                    )),
+                    None => diagnostic,
+                }
+            }
+            ScannerError::UnterminatedString(_) => diagnostic
+                .with_primary_label("string starts here")
+                .with_help("close the string with a `\"`"),
+            ScannerError::UnterminatedCharacter(_) => diagnostic
+                .with_primary_label("character literal starts here")
+                .with_help("close the character literal with a `'` on the same line"),
+            ScannerError::InvalidEscape(..) => diagnostic
+                .with_primary_label("invalid escape")
+                .with_note(
+                    "valid escapes are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\\"`, `\\'`, `\\{`, `\\}`, \
+                     `\\x00` to `\\x7F` and `\\u{0}` to `\\u{10FFFF}`",
+                ),
+            ScannerError::EmptyCharacter(_) => diagnostic.with_primary_label("empty character literal"),
+            ScannerError::OverlongCharacter(_) => diagnostic
+                .with_primary_label("more than one character")
+                .with_help("use double quotes for a string literal"),
+            ScannerError::MalformedNumber(_) => diagnostic.with_primary_label("malformed number"),
+            ScannerError::NumberOverflow(_) => diagnostic.with_primary_label("out of range"),
+            ScannerError::UnterminatedBlockComment(_) => diagnostic
+                .with_primary_label("block comment starts here")
+                .with_note("block comments nest, every `/*` needs its own `*/`"),
+        }
+    }
+}
+
+/// What the scanner is in the middle of, when scanning a string with interpolations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// The text of a string which was opened at the given position.
+    String(Position),
+    /// An interpolation in a string, with the number of braces opened inside it.
+    Interpolation(usize),
 }
 
 #[derive(Debug)]
@@ -28,32 +387,54 @@ pub struct Scanner {
     cont: String,
     cur_pos: Position,
     cur_char: char,
+    tok_start: Position,
+    leading: Vec<Trivia>,
+    trailing: Vec<Trivia>,
+    lossless: bool,
+    finished: bool,
+    warnings: Vec<ScannerWarning>,
+    skeletons: HashMap<String, (String, Span)>,
+    modes: Vec<Mode>,
+    fragment: Option<(Position, Result<Token, ScannerError>)>,
+    file: FileId,
 }
 
 impl Scanner {
     /// Returns a new scanner structure with the given values.
+    /// The content is kept as given, so spans always map back to it.
     ///
     /// # Errors
-    /// `ScannerError::EndOfContent`: If the given position's index is higher than content's length.
+    /// `ScannerError::EndOfContent`: If the given position's index is higher than content's length,
+    /// or is not on a character boundary.
     ///
     /// # Examples
     /// ```
     /// let scanner = scrawlc::Scanner::with_position("example content", &scrawlc::Position::default()).unwrap();
     ///
-    /// assert_eq!(scanner.content(), &format!("example content{}{}", scrawlc::LF, scrawlc::ETX));
+    /// assert_eq!(scanner.content(), "example content");
     /// assert_eq!(scanner.current_position(), &scrawlc::Position::new(0, 0, 0));
     /// assert_eq!(scanner.current_character(), 'e');
     /// ```
     pub fn with_position(content: &str, position: &Position) -> Result<Self, ScannerError> {
         let mut s = Scanner {
-            cont: format!("{}{}{}", content, LF, ETX),
-            cur_pos: position.clone(),
+            cont: content.to_string(),
+            cur_pos: *position,
             cur_char: ' ',
+            tok_start: *position,
+            leading: Vec::new(),
+            trailing: Vec::new(),
+            lossless: false,
+            finished: false,
+            warnings: Vec::new(),
+            skeletons: HashMap::new(),
+            modes: Vec::new(),
+            fragment: None,
+            file: FileId::default(),
         };
 
-        s.cur_char = match s.cont.chars().nth(s.cur_pos.index) {
-            Some(character) => character,
-            None => return Err(ScannerError::EndOfContent(s.cur_pos)),
+        s.cur_char = match s.cont.get(s.cur_pos.index..) {
+            Some(rest) => rest.chars().next().unwrap_or(NUL),
+            None => return Err(ScannerError::EndOfContent(Span::new(s.cur_pos, s.cur_pos))),
         };
 
         Ok(s)
@@ -62,13 +443,13 @@ impl Scanner {
     /// Returns a new scanner structure with empty values.
     ///
     /// # Errors
-    /// `ScannerError::EndOfContent`: If the given content is empty.
+    /// `ScannerError::EndOfContent`: Never, as scanning starts at the beginning of the content.
     ///
     /// # Examples
     /// ```
     /// let scanner = scrawlc::Scanner::new("example content").unwrap();
     ///
-    /// assert_eq!(scanner.content(), &format!("example content{}{}", scrawlc::LF, scrawlc::ETX));
+    /// assert_eq!(scanner.content(), "example content");
     /// assert_eq!(scanner.current_position(), &scrawlc::Position::new(0, 0, 0));
     /// assert_eq!(scanner.current_character(), 'e');
     /// ```
@@ -82,9 +463,9 @@ impl Scanner {
     /// ```
     /// let scanner = scrawlc::Scanner::new("example content").unwrap();
     ///
-    /// assert_eq!(scanner.content(), &format!("example content{}{}", scrawlc::LF, scrawlc::ETX));
+    /// assert_eq!(scanner.content(), "example content");
     /// ```
-    pub fn content(&self) -> &String {
+    pub fn content(&self) -> &str {
         &self.cont
     }
 
@@ -112,26 +493,111 @@ impl Scanner {
         self.cur_char
     }
 
-    /// Peeks the next character.
-    fn peek_next(&self) -> Result<char, ScannerError> {
-        match self.cont.chars().nth(self.cur_pos.index + 1) {
-            Some(new_character) => Ok(new_character),
-            None => return Err(ScannerError::EndOfContent(self.cur_pos)),
-        }
+    /// Returns the file the scanned content belongs to.
+    ///
+    /// # Examples
+    /// ```
+    /// let scanner = scrawlc::Scanner::new("example content").unwrap();
+    ///
+    /// assert_eq!(scanner.file(), scrawlc::FileId::NONE);
+    /// ```
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    /// Sets the file the scanned content belongs to, so the spans of tokens and errors refer to it.
+    /// Until it is set, spans refer to no file, `FileId::NONE`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "let x = 1;");
+    ///
+    /// let mut scanner = scrawlc::Scanner::new(source_map.file(file).unwrap().content()).unwrap();
+    /// scanner.set_file(file);
+    ///
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[1].span().file, file);
+    /// assert_eq!(source_map.location(tokens[1].span()).unwrap(), "main.scrawl:1:5");
+    /// ```
+    pub fn set_file(&mut self, file: FileId) {
+        self.file = file;
+    }
+
+    /// Returns the warnings found while scanning so far.
+    /// Identifiers that mix scripts, or that look like a different identifier seen before,
+    /// are scanned as usual but reported here.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("let ѕсоре = 1; let scope = 2;").unwrap();
+    /// scanner.scan().unwrap();
+    ///
+    /// assert_eq!(
+    ///     scanner.warnings()[0].to_string(),
+    ///     "identifier `scope` is confusable with `ѕсоре`"
+    /// );
+    /// ```
+    pub fn warnings(&self) -> &[ScannerWarning] {
+        &self.warnings
+    }
+
+    /// Returns `true` if the scanner keeps whitespace and comments as token trivia.
+    ///
+    /// # Examples
+    /// ```
+    /// let scanner = scrawlc::Scanner::new("example content").unwrap();
+    ///
+    /// assert!(!scanner.is_lossless());
+    /// ```
+    pub fn is_lossless(&self) -> bool {
+        self.lossless
+    }
+
+    /// Sets whether the scanner keeps whitespace and comments as token trivia.
+    ///
+    /// In lossless mode every token carries the trivia around it, and an `end of file` token
+    /// carries the trivia at the end of the content; so concatenating the full text of all
+    /// tokens reproduces the content byte-for-byte.
+    /// A token's trailing trivia holds the whitespace and line comment after it on the same line,
+    /// its leading trivia holds everything else since the previous token.
+    ///
+    /// # Examples
+    /// ```
+    /// let content = "a = 1; // one\n/* two */ b = 2;\n";
+    /// let mut scanner = scrawlc::Scanner::new(content).unwrap();
+    /// scanner.set_lossless(true);
+    ///
+    /// let tokens = scanner.scan().unwrap();
+    /// let source: String = tokens.iter().map(|token| token.full_span().slice(content)).collect();
+    ///
+    /// assert_eq!(source, content);
+    /// assert_eq!(tokens.last().unwrap().kind(), scrawlc::TokenKind::Eof);
+    /// ```
+    pub fn set_lossless(&mut self, lossless: bool) {
+        self.lossless = lossless;
+    }
+
+    /// Returns `true` if the scanner has reached the end of the given content.
+    fn is_at_end(&self) -> bool {
+        self.cur_pos.index >= self.cont.len()
     }
 
-    /// This function solves a bug.
-    // ! Do not touch it, and do not try to solve the bug.
-    // ! I fear it may cause bigger bugs.
-    fn stay(&self) -> Result<Position, ScannerError> {
-        Ok(self.cur_pos)
+    /// Peeks the next character, which is `NUL` past the end of the content.
+    fn peek_next(&self) -> char {
+        let mut rest = self.cont[self.cur_pos.index..].chars();
+
+        rest.next();
+        rest.next().unwrap_or(NUL)
     }
 
     /// Advances the scanner by one.
     /// Returns the old position if succeeds.
+    /// At the end of the content the current character is `NUL`.
     ///
     /// # Errors
-    /// `ScannerError::EndOfContent`: If cannot get the next character.
+    /// `ScannerError::EndOfContent`: If the scanner is already at the end of the content.
     ///
     /// # Examples
     /// ```
@@ -144,428 +610,1077 @@ impl Scanner {
     ///
     /// assert_eq!(scanner.current_position().index, 1);
     /// assert_eq!(scanner.current_character(), 'x');
+    ///
+    /// let mut scanner = scrawlc::Scanner::new("a").unwrap();
+    /// scanner.advance().unwrap();
+    ///
+    /// assert_eq!(scanner.current_character(), '\0');
+    /// assert!(scanner.advance().is_err());
     /// ```
     pub fn advance(&mut self) -> Result<Position, ScannerError> {
-        let clone = self.cur_pos.clone();
+        if self.is_at_end() {
+            return Err(ScannerError::EndOfContent(
+                self.span(self.cur_pos, self.cur_pos),
+            ));
+        }
+
+        let clone = self.cur_pos;
 
         self.cur_pos.advance(self.cur_char);
-        self.cur_char = match self.cont.chars().nth(self.cur_pos.index) {
-            Some(new_character) => new_character,
-            None => return Err(ScannerError::EndOfContent(self.cur_pos)),
-        };
+        self.cur_char = self.cont[self.cur_pos.index..]
+            .chars()
+            .next()
+            .unwrap_or(NUL);
 
         Ok(clone)
     }
 
-    /// Scans the content.
+    /// Scans the whole content.
     /// See the scanner documentation for more information and detailed examples.
     ///
     /// # Errors
     /// `ScannerError::EndOfContent`: If advancing fails.
     /// `ScannerError::UnsupportedCharacter`: If the character is unsupported/unknown.
+    /// `ScannerError::UnterminatedString`: If a string literal is not closed.
+    /// `ScannerError::UnterminatedCharacter`: If a character literal is not closed before the line ends.
+    /// `ScannerError::InvalidEscape`: If a literal contains an unknown or malformed escape sequence.
+    /// `ScannerError::EmptyCharacter`: If a character literal is empty.
+    /// `ScannerError::OverlongCharacter`: If a character literal holds more than one character.
+    /// `ScannerError::MalformedNumber`: If a number literal has invalid digits, exponent or suffix.
+    /// `ScannerError::NumberOverflow`: If a number literal does not fit in its type.
+    /// `ScannerError::UnterminatedBlockComment`: If a block comment is not closed.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("import IO;").unwrap();
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[1].span().slice(scanner.content()), "IO");
+    /// assert_eq!(tokens[1].span().start, scrawlc::Position::new(7, 0, 7));
+    /// assert_eq!(tokens[1].span().end, scrawlc::Position::new(9, 0, 9));
+    /// ```
     pub fn scan(&mut self) -> Result<Vec<Token>, ScannerError> {
-        let mut _result: Vec<Token> = Vec::new();
+        self.tokens().collect()
+    }
 
-        while self.peek_next()? != ETX {
-            if IDENTIFIER_SET.contains(self.cur_char) {
-                let mut identifier = String::new();
+    /// Scans the whole content without stopping at the first error.
+    /// The input consumed by a failed token is turned into an `error` token and scanning
+    /// continues after it; every error is returned together with the tokens.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a $ b $").unwrap();
+    /// let (tokens, errors) = scanner.scan_recovering();
+    ///
+    /// assert_eq!(tokens.len(), 5);
+    /// assert_eq!(tokens[1].kind(), scrawlc::TokenKind::Error);
+    /// assert_eq!(tokens[1].value(), Some("$"));
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    pub fn scan_recovering(&mut self) -> (Vec<Token>, Vec<ScannerError>) {
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        loop {
+            match self.next_token() {
+                Ok(Some(token)) => tokens.push(token),
+                Ok(None) => break,
+                Err(error) => {
+                    let span = self.span(self.tok_start, self.cur_pos.max(self.tok_start));
+                    let text = self
+                        .cont
+                        .get(span.start.index..span.end.index)
+                        .unwrap_or("");
+                    let leading = std::mem::take(&mut self.leading);
+
+                    tokens.push(
+                        Token::new(TokenKind::Error, Some(text), &span)
+                            .with_trivia(leading, Vec::new()),
+                    );
+                    errors.push(error);
+                }
+            }
+        }
 
-                while IDENTIFIER_SET.contains(self.cur_char) || NUMBER_SET.contains(self.cur_char) {
-                    identifier.push(self.cur_char);
+        (tokens, errors)
+    }
 
-                    self.advance()?;
-                }
+    /// Returns an iterator which scans the tokens on demand.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a + b; c").unwrap();
+    /// let statement: Vec<_> = scanner
+    ///     .tokens()
+    ///     .map(|token| token.unwrap())
+    ///     .take_while(|token| token.kind() != scrawlc::TokenKind::Semicolon)
+    ///     .collect();
+    ///
+    /// assert_eq!(statement.len(), 3);
+    /// assert_eq!(scanner.next_token().unwrap().unwrap().value(), Some("c"));
+    /// ```
+    pub fn tokens(&mut self) -> Tokens<'_> {
+        Tokens { scanner: self }
+    }
 
-                _result.push(Token::new("identifier", &identifier, &self.cur_pos))
-            } else if NUMBER_SET.contains(self.cur_char) {
-                let mut number = String::new();
+    /// Scans the next token.
+    /// The last token is always an `end of file` token, after which `None` is returned.
+    ///
+    /// After an unsupported character the scanner moves past it, so scanning can continue;
+    /// after an `EndOfContent` error the scanner is finished and only returns `None`.
+    ///
+    /// # Errors
+    /// `ScannerError::EndOfContent`: If advancing fails.
+    /// `ScannerError::UnsupportedCharacter`: If the character is unsupported/unknown.
+    /// `ScannerError::UnterminatedString`: If a string literal is not closed.
+    /// `ScannerError::UnterminatedCharacter`: If a character literal is not closed before the line ends.
+    /// `ScannerError::InvalidEscape`: If a literal contains an unknown or malformed escape sequence.
+    /// `ScannerError::EmptyCharacter`: If a character literal is empty.
+    /// `ScannerError::OverlongCharacter`: If a character literal holds more than one character.
+    /// `ScannerError::MalformedNumber`: If a number literal has invalid digits, exponent or suffix.
+    /// `ScannerError::NumberOverflow`: If a number literal does not fit in its type.
+    /// `ScannerError::UnterminatedBlockComment`: If a block comment is not closed.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("import IO;").unwrap();
+    ///
+    /// assert_eq!(scanner.next_token().unwrap().unwrap().kind(), scrawlc::TokenKind::Import);
+    /// assert_eq!(scanner.next_token().unwrap().unwrap().value(), Some("IO"));
+    /// assert_eq!(scanner.next_token().unwrap().unwrap().kind(), scrawlc::TokenKind::Semicolon);
+    /// assert_eq!(scanner.next_token().unwrap().unwrap().kind(), scrawlc::TokenKind::Eof);
+    /// assert!(scanner.next_token().unwrap().is_none());
+    /// ```
+    pub fn next_token(&mut self) -> Result<Option<Token>, ScannerError> {
+        if self.finished {
+            return Ok(None);
+        }
 
-                while NUMBER_SET.contains(self.cur_char) {
-                    number.push(self.cur_char);
+        let result = self.scan_token();
 
-                    self.advance()?;
-                }
+        self.finished = match &result {
+            Ok(token) => token.kind() == TokenKind::Eof,
+            Err(error) => matches!(error, ScannerError::EndOfContent(_)),
+        };
 
-                _result.push(Token::new("number", &number, &self.cur_pos))
-            } else {
-                match self.cur_char {
-                    ETX => break,
-                    TAB => self.advance()?,
-                    LF => self.advance()?,
-                    CR => self.advance()?,
-                    ' ' => self.advance()?,
-                    '(' => {
-                        _result.push(Token::new("(", "(", &self.cur_pos));
-
-                        self.advance()?
-                    }
-                    ')' => {
-                        _result.push(Token::new(")", ")", &self.cur_pos));
+        result.map(Some)
+    }
 
-                        self.advance()?
-                    }
-                    '{' => {
-                        _result.push(Token::new("{", "{", &self.cur_pos));
+    /// Scans a single token together with the trivia around it.
+    /// The text of a string with interpolations has no trivia.
+    fn scan_token(&mut self) -> Result<Token, ScannerError> {
+        if let Some(Mode::String(start)) = self.modes.last().copied() {
+            if let Some((fragment_start, fragment)) = self.fragment.take() {
+                self.tok_start = fragment_start;
 
-                        self.advance()?
-                    }
-                    '}' => {
-                        _result.push(Token::new("}", "}", &self.cur_pos));
+                return fragment;
+            }
 
-                        self.advance()?
-                    }
-                    '[' => {
-                        _result.push(Token::new("[", "[", &self.cur_pos));
+            self.tok_start = self.cur_pos;
 
-                        self.advance()?
-                    }
-                    ']' => {
-                        _result.push(Token::new("]", "]", &self.cur_pos));
+            return self.scan_string_part(start);
+        }
+
+        self.scan_trivia(false)?;
+        self.tok_start = self.cur_pos;
+
+        if let Some(start) = self.modes.iter().find_map(|mode| match mode {
+            Mode::String(start) if self.is_at_end() => Some(*start),
+            _ => None,
+        }) {
+            self.modes.clear();
+
+            return Err(ScannerError::UnterminatedString(
+                self.span(start, self.cur_pos),
+            ));
+        }
+
+        if self.is_at_end() {
+            let span = self.span(self.cur_pos, self.cur_pos);
+            let leading = std::mem::take(&mut self.leading);
+
+            return Ok(Token::new(TokenKind::Eof, None, &span).with_trivia(leading, Vec::new()));
+        }
 
-                        self.advance()?
+        let token = match self.scan_token_kind(self.tok_start) {
+            // A string left open in an interpolation leaves the enclosing strings open too, so the
+            // outermost one is reported rather than both.
+            Err(ScannerError::UnterminatedString(span)) if self.is_at_end() => {
+                let start = self.modes.iter().find_map(|mode| match mode {
+                    Mode::String(start) => Some(*start),
+                    _ => None,
+                });
+
+                self.modes.clear();
+
+                return Err(ScannerError::UnterminatedString(match start {
+                    Some(start) => self.span(start, self.cur_pos),
+                    None => span,
+                }));
+            }
+            result => result?,
+        };
+
+        if !matches!(self.modes.last(), Some(Mode::String(_))) {
+            self.scan_trivia(true)?;
+        }
+
+        let leading = std::mem::take(&mut self.leading);
+        let trailing = std::mem::take(&mut self.trailing);
+
+        Ok(token.with_trivia(leading, trailing))
+    }
+
+    /// Scans the trivia before a token, or the trivia after a token on the same line.
+    /// The trivia is only kept in lossless mode.
+    fn scan_trivia(&mut self, trailing: bool) -> Result<(), ScannerError> {
+        loop {
+            let start = self.cur_pos;
+            self.tok_start = start;
+
+            let kind = match self.cur_char {
+                _ if self.is_at_end() => break,
+                LF if !trailing => self.single(TriviaKind::Newline)?,
+                BOM if self.cur_pos.index == 0 => self.single(TriviaKind::ByteOrderMark)?,
+                '#' if self.is_at_shebang() => {
+                    while self.cur_char != LF && !self.is_at_end() {
+                        self.advance()?;
                     }
-                    ';' => {
-                        _result.push(Token::new(";", ";", &self.cur_pos));
 
-                        self.advance()?
+                    TriviaKind::Shebang
+                }
+                TAB | CR | ' ' => {
+                    while matches!(self.cur_char, TAB | CR | ' ') {
+                        self.advance()?;
                     }
-                    ',' => {
-                        _result.push(Token::new(",", ",", &self.cur_pos));
 
-                        self.advance()?
+                    TriviaKind::Whitespace
+                }
+                '/' if self.peek_next() == '/' && !self.is_at_doc_comment() => {
+                    while self.cur_char != LF && !self.is_at_end() {
+                        self.advance()?;
                     }
-                    '.' => {
-                        _result.push(Token::new(".", ".", &self.cur_pos));
 
-                        self.advance()?
+                    TriviaKind::LineComment
+                }
+                '/' if !trailing && self.peek_next() == '*' => {
+                    self.advance()?;
+                    self.skip_block_comment(start)?;
+
+                    TriviaKind::BlockComment
+                }
+                _ => break,
+            };
+
+            if self.lossless {
+                let trivia = Trivia::new(kind, &self.span(start, self.cur_pos));
+
+                if trailing {
+                    self.trailing.push(trivia);
+                } else {
+                    self.leading.push(trivia);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the scanner is at a `#!` line at the start of the content, optionally after
+    /// a byte order mark. A `#![` starts an inner attribute instead.
+    fn is_at_shebang(&self) -> bool {
+        matches!(&self.cont[..self.cur_pos.index], "" | "\u{FEFF}")
+            && self.cont[self.cur_pos.index..].starts_with("#!")
+            && !self.cont[self.cur_pos.index..].starts_with("#![")
+    }
+
+    /// Returns `true` if the scanner is at the start of a `///` or `//!` documentation comment.
+    fn is_at_doc_comment(&self) -> bool {
+        let rest = &self.cont[self.cur_pos.index..];
+
+        rest.starts_with("//!") || (rest.starts_with("///") && !rest.starts_with("////"))
+    }
+
+    /// Scans a single token, starting at its first character.
+    fn scan_token_kind(&mut self, start: Position) -> Result<Token, ScannerError> {
+        if self.is_at_raw_string() {
+            self.scan_raw_string(start)
+        } else if is_identifier_start(self.cur_char) {
+            let mut identifier = String::new();
+
+            while is_identifier_continue(self.cur_char) {
+                identifier.push(self.cur_char);
+
+                self.advance()?;
+            }
+
+            let span = self.span(start, self.cur_pos);
+            let identifier: String = identifier.nfc().collect();
+
+            match TokenKind::keyword(&identifier) {
+                Some(keyword) => Ok(Token::new(keyword, None, &span)),
+                None => {
+                    self.check_identifier(&identifier, &span);
+
+                    Ok(Token::new(TokenKind::Identifier, Some(&identifier), &span))
+                }
+            }
+        } else if NUMBER_SET.contains(self.cur_char) {
+            self.scan_number(start)
+        } else {
+            let kind = match self.cur_char {
+                '{' => {
+                    if let Some(Mode::Interpolation(depth)) = self.modes.last_mut() {
+                        *depth += 1;
                     }
-                    '@' => {
-                        _result.push(Token::new("@", "@", &self.cur_pos));
 
-                        self.advance()?
+                    self.single(TokenKind::LeftBrace)?
+                }
+                '}' => match self.modes.last_mut() {
+                    Some(Mode::Interpolation(0)) => {
+                        self.modes.pop();
+
+                        self.single(TokenKind::InterpolationClose)?
                     }
-                    '#' => {
-                        _result.push(Token::new("#", "#", &self.cur_pos));
+                    Some(Mode::Interpolation(depth)) => {
+                        *depth -= 1;
 
-                        self.advance()?
+                        self.single(TokenKind::RightBrace)?
                     }
-                    '=' => {
-                        self.advance()?;
+                    _ => self.single(TokenKind::RightBrace)?,
+                },
+                '/' if self.peek_next() == '/' => {
+                    self.advance()?;
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("==", "==", &self.cur_pos));
+                    return self.scan_doc_comment(start);
+                }
+                '"' if self.cont[self.cur_pos.index..].starts_with("\"\"\"") => {
+                    return self.scan_multiline_string(start)
+                }
+                '"' => return self.scan_string(start),
+                '\'' => return self.scan_character(start),
+                _ => match self.scan_operator()? {
+                    Some(kind) => kind,
+                    None => {
+                        let character = self.cur_char;
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("=", "=", &self.cur_pos));
+                        self.advance()?;
 
-                                self.stay()?
-                            }
-                        }
+                        return Err(ScannerError::UnsupportedCharacter(
+                            character,
+                            self.span(start, self.cur_pos),
+                        ));
                     }
-                    '+' => {
-                        self.advance()?;
+                },
+            };
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("+=", "+=", &self.cur_pos));
+            Ok(Token::new(kind, None, &self.span(start, self.cur_pos)))
+        }
+    }
 
-                                self.advance()?
-                            }
-                            '+' => {
-                                _result.push(Token::new("++", "++", &self.cur_pos));
+    /// Scans the longest punctuator or operator at the current position, see `OPERATORS`.
+    /// Returns `None` if no punctuator or operator starts there.
+    fn scan_operator(&mut self) -> Result<Option<TokenKind>, ScannerError> {
+        let rest = &self.cont[self.cur_pos.index..];
+
+        let Some((text, kind)) = OPERATORS
+            .iter()
+            .filter(|(text, _)| rest.starts_with(text))
+            .max_by_key(|(text, _)| text.len())
+        else {
+            return Ok(None);
+        };
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("+", "+", &self.cur_pos));
+        for _ in 0..text.len() {
+            self.advance()?;
+        }
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '>' => {
-                        self.advance()?;
+        Ok(Some(*kind))
+    }
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new(">=", ">=", &self.cur_pos));
-
-                                self.advance()?
-                            }
-                            '>' => {
-                                self.advance()?;
-
-                                match self.cur_char {
-                                    '=' => {
-                                        _result.push(Token::new(">>=", ">>=", &self.cur_pos));
-
-                                        self.advance()?
-                                    }
-                                    _ => {
-                                        _result.push(Token::new(">>", ">>", &self.cur_pos));
-
-                                        self.stay()?
-                                    }
-                                }
-                            }
-                            _ => {
-                                _result.push(Token::new(">", ">", &self.cur_pos));
-
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '-' => {
-                        self.advance()?;
+    /// Records a warning if an identifier mixes scripts, or if it is confusable with an identifier
+    /// seen before. Only identifiers with non-ASCII characters are reported.
+    fn check_identifier(&mut self, identifier: &str, span: &Span) {
+        if !identifier.is_ascii() && !identifier.is_single_script() {
+            self.warnings.push(ScannerWarning::MixedScriptIdentifier(
+                identifier.to_string(),
+                *span,
+            ));
+        }
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("-=", "-=", &self.cur_pos));
+        let skeleton: String = skeleton(identifier).collect();
+
+        match self.skeletons.get(&skeleton) {
+            Some((other, other_span))
+                if other != identifier && !(other.is_ascii() && identifier.is_ascii()) =>
+            {
+                self.warnings.push(ScannerWarning::ConfusableIdentifier(
+                    identifier.to_string(),
+                    other.clone(),
+                    *span,
+                    *other_span,
+                ));
+            }
+            Some(_) => {}
+            None => {
+                self.skeletons
+                    .insert(skeleton, (identifier.to_string(), *span));
+            }
+        }
+    }
 
-                                self.advance()?
-                            }
-                            '-' => {
-                                _result.push(Token::new("--", "--", &self.cur_pos));
+    /// Scans a `///` or `//!` documentation comment, starting at its second slash.
+    fn scan_doc_comment(&mut self, start: Position) -> Result<Token, ScannerError> {
+        self.advance()?;
 
-                                self.advance()?
-                            }
-                            '>' => {
-                                _result.push(Token::new("->", "->", &self.cur_pos));
+        let kind = match self.cur_char {
+            '!' => TokenKind::InnerDocComment,
+            _ => TokenKind::DocComment,
+        };
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("-", "-", &self.cur_pos));
+        self.advance()?;
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '<' => {
-                        self.advance()?;
+        let mut text = String::new();
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("<=", "<=", &self.cur_pos));
-
-                                self.advance()?
-                            }
-                            '<' => {
-                                self.advance()?;
-
-                                match self.cur_char {
-                                    '=' => {
-                                        _result.push(Token::new("<<=", "<<=", &self.cur_pos));
-
-                                        self.advance()?
-                                    }
-                                    _ => {
-                                        _result.push(Token::new("<<", "<<", &self.cur_pos));
-
-                                        self.stay()?
-                                    }
-                                }
-                            }
-                            _ => {
-                                _result.push(Token::new("<", "<", &self.cur_pos));
-
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '*' => {
-                        self.advance()?;
+        while self.cur_char != LF
+            && !(self.cur_char == CR && self.peek_next() == LF)
+            && !self.is_at_end()
+        {
+            text.push(self.cur_char);
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("*=", "*=", &self.cur_pos));
+            self.advance()?;
+        }
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("*", "*", &self.cur_pos));
+        Ok(Token::new(
+            kind,
+            Some(&text),
+            &self.span(start, self.cur_pos),
+        ))
+    }
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '!' => {
-                        self.advance()?;
+    /// Skips a block comment, starting at its asterisk.
+    /// Block comments can be nested, every `/*` must be closed by its own `*/`.
+    fn skip_block_comment(&mut self, start: Position) -> Result<(), ScannerError> {
+        self.advance()?;
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("!=", "!=", &self.cur_pos));
+        let opening = self.span(start, self.cur_pos);
+        let mut depth = 1;
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("!", "!", &self.cur_pos));
+        while depth > 0 {
+            if self.is_at_end() {
+                return Err(ScannerError::UnterminatedBlockComment(opening));
+            }
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '/' => {
-                        self.advance()?;
+            match (self.cur_char, self.peek_next()) {
+                ('/', '*') => {
+                    depth += 1;
 
-                        match self.cur_char {
-                            '/' => {
-                                self.advance()?;
+                    self.advance()?;
+                }
+                ('*', '/') => {
+                    depth -= 1;
 
-                                while self.peek_next()? != LF || self.peek_next()? != ETX {
-                                    self.advance()?;
-                                }
+                    self.advance()?;
+                }
+                _ => {}
+            }
 
-                                self.stay()?
-                            }
-                            '=' => {
-                                _result.push(Token::new("/=", "/=", &self.cur_pos));
+            self.advance()?;
+        }
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("/", "/", &self.cur_pos));
+        Ok(())
+    }
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '~' => {
-                        _result.push(Token::new("~", "~", &self.cur_pos));
+    /// Scans a number literal, starting at its first digit.
+    /// Integers may have a `0x`, `0o` or `0b` prefix, floats may have a fraction and an exponent,
+    /// both may contain `_` separators and end with a type suffix such as `u8` or `f32`.
+    fn scan_number(&mut self, start: Position) -> Result<Token, ScannerError> {
+        let mut radix = 10;
+        let mut digits = String::new();
+        let mut malformed = false;
+        let mut is_float = false;
+
+        if self.cur_char == '0' {
+            radix = match self.peek_next() {
+                'x' => 16,
+                'o' => 8,
+                'b' => 2,
+                _ => 10,
+            };
+
+            if radix != 10 {
+                self.advance()?;
+                self.advance()?;
+            }
+        }
 
-                        self.advance()?
-                    }
-                    '&' => {
-                        self.advance()?;
+        malformed |= !self.scan_digits(radix, &mut digits)?;
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("&=", "&=", &self.cur_pos));
+        if radix == 10 {
+            if self.cur_char == '.' && self.peek_next().is_ascii_digit() {
+                is_float = true;
+                digits.push('.');
 
-                                self.advance()?
-                            }
-                            '&' => {
-                                _result.push(Token::new("&&", "&&", &self.cur_pos));
+                self.advance()?;
+                self.scan_digits(10, &mut digits)?;
+            }
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("&", "&", &self.cur_pos));
+            if matches!(self.cur_char, 'e' | 'E')
+                && matches!(self.peek_next(), '0'..='9' | '+' | '-' | '_')
+            {
+                is_float = true;
+                digits.push('e');
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    '?' => {
-                        _result.push(Token::new("?", "?", &self.cur_pos));
+                self.advance()?;
 
-                        self.advance()?
-                    }
-                    '|' => {
-                        self.advance()?;
+                if matches!(self.cur_char, '+' | '-') {
+                    digits.push(self.cur_char);
 
-                        match self.cur_char {
-                            '=' => {
-                                _result.push(Token::new("|=", "|=", &self.cur_pos));
+                    self.advance()?;
+                }
 
-                                self.advance()?
-                            }
-                            '|' => {
-                                _result.push(Token::new("||", "||", &self.cur_pos));
+                malformed |=
+                    !self.scan_digits(10, &mut digits)? || digits.ends_with(['e', '+', '-']);
+            }
+        }
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("|", "|", &self.cur_pos));
+        let mut suffix = String::new();
 
-                                self.stay()?
-                            }
-                        }
-                    }
-                    ':' => {
-                        self.advance()?;
+        if is_identifier_start(self.cur_char) {
+            while is_identifier_continue(self.cur_char) {
+                suffix.push(self.cur_char);
 
-                        match self.cur_char {
-                            ':' => {
-                                _result.push(Token::new("::", "::", &self.cur_pos));
+                self.advance()?;
+            }
+        }
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new(":", ":", &self.cur_pos));
+        let span = self.span(start, self.cur_pos);
+        let suffix = match suffix.as_str() {
+            "" => None,
+            suffix => match NumberSuffix::from_suffix(suffix) {
+                Some(suffix) => Some(suffix),
+                None => return Err(ScannerError::MalformedNumber(span)),
+            },
+        };
 
-                                self.stay()?
-                            }
-                        }
+        if malformed || digits.is_empty() {
+            return Err(ScannerError::MalformedNumber(span));
+        }
+
+        if is_float || suffix.is_some_and(|suffix| suffix.is_float()) {
+            if radix != 10 || suffix.is_some_and(|suffix| !suffix.is_float()) {
+                return Err(ScannerError::MalformedNumber(span));
+            }
+
+            // An `f32` is parsed as one, as rounding through an `f64` may leave the range.
+            let value = match suffix {
+                Some(NumberSuffix::F32) => digits.parse::<f32>().map(f64::from),
+                _ => digits.parse::<f64>(),
+            };
+            let value = match value {
+                Ok(value) => value,
+                Err(_) => return Err(ScannerError::MalformedNumber(span)),
+            };
+
+            // A literal too small for the type would silently become zero.
+            let mantissa = digits.split('e').next().unwrap_or_default();
+            let underflows = value == 0.0 && mantissa.chars().any(|c| matches!(c, '1'..='9'));
+
+            if value.is_infinite() || underflows {
+                return Err(ScannerError::NumberOverflow(span));
+            }
+
+            Ok(Token::new(TokenKind::Float, None, &span)
+                .with_literal(Literal::Float(value, suffix))
+                .with_raw(span.slice(&self.cont)))
+        } else {
+            let value = match u128::from_str_radix(&digits, radix) {
+                Ok(value) => value,
+                Err(_) => return Err(ScannerError::NumberOverflow(span)),
+            };
+
+            if suffix
+                .and_then(|suffix| suffix.max_integer())
+                .is_some_and(|max| value > max)
+            {
+                return Err(ScannerError::NumberOverflow(span));
+            }
+
+            Ok(Token::new(TokenKind::Integer, None, &span)
+                .with_literal(Literal::Integer(value, suffix))
+                .with_raw(span.slice(&self.cont)))
+        }
+    }
+
+    /// Scans the digits of a number in the given radix into `digits`, skipping `_` separators.
+    /// Returns `false` if a decimal digit that does not belong to the radix was found.
+    fn scan_digits(&mut self, radix: u32, digits: &mut String) -> Result<bool, ScannerError> {
+        let mut valid = true;
+
+        while self.cur_char == '_'
+            || (radix == 16 && self.cur_char.is_ascii_hexdigit())
+            || self.cur_char.is_ascii_digit()
+        {
+            if self.cur_char != '_' {
+                valid &= self.cur_char.is_digit(radix);
+                digits.push(self.cur_char);
+            }
+
+            self.advance()?;
+        }
+
+        Ok(valid)
+    }
+
+    /// Scans a string literal, starting at the opening quote.
+    /// Invalid escapes do not stop the literal; the first one is returned once the literal is closed.
+    ///
+    /// A string with an interpolation is returned as a sequence of tokens instead: its opening
+    /// quote, then its fragments and interpolations, see `Scanner::scan_string_part`. The text
+    /// scanned before the first `{` is kept as the fragment following the opening quote.
+    fn scan_string(&mut self, start: Position) -> Result<Token, ScannerError> {
+        self.advance()?;
+        let text_start = self.cur_pos;
+        let mut string = String::new();
+        let mut invalid_escape = None;
+
+        while self.cur_char != '"' {
+            if self.cur_char == '{' {
+                if text_start != self.cur_pos {
+                    let span = self.span(text_start, self.cur_pos);
+                    let fragment = match invalid_escape {
+                        Some(error) => Err(error),
+                        None => Ok(Token::new(TokenKind::StringFragment, Some(&string), &span)
+                            .with_raw(span.slice(&self.cont))),
+                    };
+
+                    self.fragment = Some((text_start, fragment));
+                }
+
+                self.modes.push(Mode::String(start));
+
+                return Ok(Token::new(
+                    TokenKind::StringStart,
+                    None,
+                    &self.span(start, text_start),
+                ));
+            }
+
+            if self.is_at_end() {
+                return Err(ScannerError::UnterminatedString(
+                    self.span(start, self.cur_pos),
+                ));
+            }
+
+            if self.cur_char == '\\' {
+                match self.scan_escape() {
+                    Ok(character) => string.push(character),
+                    Err(error @ ScannerError::InvalidEscape(..)) => {
+                        invalid_escape.get_or_insert(error);
                     }
-                    '^' => {
-                        self.advance()?;
+                    Err(error) => return Err(error),
+                }
+            } else {
+                string.push(self.cur_char);
 
-                        match self.cur_char {
-                            '^' => {
-                                _result.push(Token::new("^=", "^=", &self.cur_pos));
+                self.advance()?;
+            }
+        }
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("^", "^", &self.cur_pos));
+        self.advance()?;
 
-                                self.stay()?
-                            }
-                        }
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
+
+        let span = self.span(start, self.cur_pos);
+
+        Ok(Token::new(TokenKind::String, Some(&string), &span)
+            .with_raw(span.slice(&self.cont))
+            .with_style(StringStyle::Quoted))
+    }
+
+    /// Scans the next part of a string with interpolations, which was opened at `start`:
+    /// a fragment of text, the `{` opening an interpolation, or the closing quote.
+    fn scan_string_part(&mut self, start: Position) -> Result<Token, ScannerError> {
+        let part_start = self.cur_pos;
+
+        match self.cur_char {
+            '"' => {
+                self.modes.pop();
+                self.advance()?;
+
+                return Ok(Token::new(
+                    TokenKind::StringEnd,
+                    None,
+                    &self.span(part_start, self.cur_pos),
+                ));
+            }
+            '{' => {
+                self.modes.push(Mode::Interpolation(0));
+                self.advance()?;
+
+                return Ok(Token::new(
+                    TokenKind::InterpolationOpen,
+                    None,
+                    &self.span(part_start, self.cur_pos),
+                ));
+            }
+            _ => {}
+        }
+
+        let mut fragment = String::new();
+        let mut invalid_escape = None;
+
+        while self.cur_char != '"' && self.cur_char != '{' {
+            if self.is_at_end() {
+                self.modes.clear();
+
+                return Err(ScannerError::UnterminatedString(
+                    self.span(start, self.cur_pos),
+                ));
+            }
+
+            if self.cur_char == '\\' {
+                match self.scan_escape() {
+                    Ok(character) => fragment.push(character),
+                    Err(error @ ScannerError::InvalidEscape(..)) => {
+                        invalid_escape.get_or_insert(error);
                     }
-                    '%' => {
-                        self.advance()?;
+                    Err(error) => return Err(error),
+                }
+            } else {
+                fragment.push(self.cur_char);
+
+                self.advance()?;
+            }
+        }
+
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
+
+        let span = self.span(part_start, self.cur_pos);
+
+        Ok(
+            Token::new(TokenKind::StringFragment, Some(&fragment), &span)
+                .with_raw(span.slice(&self.cont)),
+        )
+    }
+
+    /// Returns `true` if the scanner is at the start of a `r"..."` or `r#"..."#` raw string.
+    fn is_at_raw_string(&self) -> bool {
+        let rest = &self.cont[self.cur_pos.index..];
+
+        rest.starts_with('r') && rest[1..].trim_start_matches('#').starts_with('"')
+    }
 
-                        match self.cur_char {
-                            '%' => {
-                                _result.push(Token::new("%=", "%=", &self.cur_pos));
+    /// Scans a raw string literal, starting at its `r`.
+    /// The content is kept as written until a quote followed by as many `#`s as were opened.
+    fn scan_raw_string(&mut self, start: Position) -> Result<Token, ScannerError> {
+        self.advance()?;
+        let mut hashes = 0;
 
-                                self.advance()?
-                            }
-                            _ => {
-                                _result.push(Token::new("%", "%", &self.cur_pos));
+        while self.cur_char == '#' {
+            hashes += 1;
+
+            self.advance()?;
+        }
+
+        self.advance()?;
+
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let mut string = String::new();
+
+        while !self.cont[self.cur_pos.index..].starts_with(&closing) {
+            if self.is_at_end() {
+                return Err(ScannerError::UnterminatedString(
+                    self.span(start, self.cur_pos),
+                ));
+            }
+
+            string.push(self.cur_char);
+
+            self.advance()?;
+        }
+
+        for _ in 0..closing.len() {
+            self.advance()?;
+        }
+
+        let span = self.span(start, self.cur_pos);
+
+        Ok(Token::new(TokenKind::String, Some(&string), &span)
+            .with_raw(span.slice(&self.cont))
+            .with_style(StringStyle::Raw(hashes)))
+    }
 
-                                self.stay()?
-                            }
+    /// Scans a `"""` multi-line string literal, starting at the first opening quote.
+    ///
+    /// A line break right after the opening quotes is dropped, and so is the last line if it only
+    /// holds the indentation of the closing quotes. The indentation shared by all lines that are
+    /// not blank, and by the closing quotes, is stripped from every line.
+    fn scan_multiline_string(&mut self, start: Position) -> Result<Token, ScannerError> {
+        for _ in 0..3 {
+            self.advance()?;
+        }
+
+        if self.cur_char == CR && self.peek_next() == LF {
+            self.advance()?;
+        }
+
+        if self.cur_char == LF {
+            self.advance()?;
+        }
+
+        // Every line holds its text, the number of whitespace characters it was indented with in
+        // the source, and whether it is blank.
+        let mut lines = vec![(String::new(), 0, true)];
+        let mut invalid_escape = None;
+
+        while !self.cont[self.cur_pos.index..].starts_with("\"\"\"") {
+            if self.is_at_end() {
+                return Err(ScannerError::UnterminatedString(
+                    self.span(start, self.cur_pos),
+                ));
+            }
+
+            let (line, indent, blank) = lines.last_mut().unwrap();
+
+            match self.cur_char {
+                LF => {
+                    lines.push((String::new(), 0, true));
+
+                    self.advance()?;
+                }
+                CR if self.peek_next() == LF => {
+                    self.advance()?;
+                }
+                TAB | ' ' if *blank => {
+                    line.push(self.cur_char);
+                    *indent += 1;
+
+                    self.advance()?;
+                }
+                '\\' => {
+                    *blank = false;
+
+                    match self.scan_escape() {
+                        Ok(character) => line.push(character),
+                        Err(error @ ScannerError::InvalidEscape(..)) => {
+                            invalid_escape.get_or_insert(error);
                         }
+                        Err(error) => return Err(error),
                     }
-                    '"' => {
-                        self.advance()?;
-                        let mut string = String::new();
+                }
+                character => {
+                    *blank = false;
+                    line.push(character);
 
-                        while self.cur_char != '"' {
-                            string.push(self.cur_char);
+                    self.advance()?;
+                }
+            }
+        }
 
-                            self.advance()?;
-                        }
+        for _ in 0..3 {
+            self.advance()?;
+        }
 
-                        _result.push(Token::new("string", &string, &self.cur_pos));
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
 
-                        self.advance()?
-                    }
-                    '\'' => {
-                        self.advance()?;
-                        let mut character = String::new();
+        let last = lines.len() - 1;
+        let closing_indent = match lines[last] {
+            (_, indent, true) if last > 0 => Some(indent),
+            _ => None,
+        };
 
-                        while self.cur_char != '\'' {
-                            character.push(self.cur_char);
+        if closing_indent.is_some() {
+            lines.pop();
+        }
 
-                            self.advance()?;
-                        }
+        let indent = lines
+            .iter()
+            .filter(|(_, _, blank)| !blank)
+            .map(|(_, indent, _)| *indent)
+            .chain(closing_indent)
+            .min()
+            .unwrap_or(0);
+
+        let string = lines
+            .iter()
+            .map(|(line, _, _)| line.chars().skip(indent).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let span = self.span(start, self.cur_pos);
+
+        Ok(Token::new(TokenKind::String, Some(&string), &span)
+            .with_raw(span.slice(&self.cont))
+            .with_style(StringStyle::Multiline))
+    }
 
-                        _result.push(Token::new("character", &character, &self.cur_pos));
+    /// Scans a character literal, starting at the opening quote.
+    /// The literal must hold exactly one character once its escapes are processed.
+    fn scan_character(&mut self, start: Position) -> Result<Token, ScannerError> {
+        self.advance()?;
+        let mut character = String::new();
+        let mut invalid_escape = None;
+
+        while self.cur_char != '\'' {
+            if self.is_at_end() || self.cur_char == LF {
+                return Err(ScannerError::UnterminatedCharacter(
+                    self.span(start, self.cur_pos),
+                ));
+            }
 
-                        self.advance()?
+            if self.cur_char == '\\' {
+                match self.scan_escape() {
+                    Ok(escaped) => character.push(escaped),
+                    Err(error @ ScannerError::InvalidEscape(..)) => {
+                        invalid_escape.get_or_insert(error);
                     }
-                    _ => return Err(ScannerError::UnsupportedCharacter(self.cur_char)),
+                    Err(error) => return Err(error),
+                }
+            } else {
+                character.push(self.cur_char);
+
+                self.advance()?;
+            }
+        }
+
+        self.advance()?;
+
+        if let Some(error) = invalid_escape {
+            return Err(error);
+        }
+
+        let span = self.span(start, self.cur_pos);
+        let mut characters = character.chars();
+
+        match (characters.next(), characters.next()) {
+            (Some(character), None) => Ok(Token::new(TokenKind::Character, None, &span)
+                .with_literal(Literal::Character(character))
+                .with_raw(span.slice(&self.cont))),
+            (None, _) => Err(ScannerError::EmptyCharacter(span)),
+            (Some(_), Some(_)) => Err(ScannerError::OverlongCharacter(span)),
+        }
+    }
+
+    /// Scans an escape sequence, starting at the backslash, and returns the escaped character.
+    ///
+    /// # Errors
+    /// `ScannerError::InvalidEscape`: If the escape sequence is unknown or malformed.
+    fn scan_escape(&mut self) -> Result<char, ScannerError> {
+        let start = self.cur_pos;
+
+        self.advance()?;
+
+        // A backslash at the end is left to the literal, which reports it as unterminated.
+        if self.is_at_end() {
+            return Ok('\\');
+        }
+
+        let escape = self.cur_char;
+        let character = match escape {
+            'n' => Some(LF),
+            't' => Some(TAB),
+            'r' => Some(CR),
+            '0' => Some('\0'),
+            '\\' | '"' | '\'' | '{' | '}' => Some(escape),
+            'x' => {
+                self.advance()?;
+
+                let digits = self.scan_hex_digits(2)?;
+
+                return match u8::from_str_radix(&digits, 16) {
+                    Ok(value) if digits.len() == 2 && value <= 0x7F => Ok(char::from(value)),
+                    _ => Err(ScannerError::InvalidEscape(
+                        escape,
+                        self.span(start, self.cur_pos),
+                    )),
                 };
             }
+            'u' => {
+                self.advance()?;
+
+                if self.cur_char != '{' {
+                    return Err(ScannerError::InvalidEscape(
+                        escape,
+                        self.span(start, self.cur_pos),
+                    ));
+                }
+
+                self.advance()?;
+
+                let digits = self.scan_hex_digits(6)?;
+
+                if self.cur_char != '}' {
+                    return Err(ScannerError::InvalidEscape(
+                        escape,
+                        self.span(start, self.cur_pos),
+                    ));
+                }
+
+                self.advance()?;
+
+                return u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or(ScannerError::InvalidEscape(
+                        escape,
+                        self.span(start, self.cur_pos),
+                    ));
+            }
+            _ => None,
+        };
+
+        self.advance()?;
+
+        character.ok_or(ScannerError::InvalidEscape(
+            escape,
+            self.span(start, self.cur_pos),
+        ))
+    }
+
+    /// Scans up to `limit` hexadecimal digits.
+    fn scan_hex_digits(&mut self, limit: usize) -> Result<String, ScannerError> {
+        let mut digits = String::new();
+
+        while digits.len() < limit && self.cur_char.is_ascii_hexdigit() && !self.is_at_end() {
+            digits.push(self.cur_char);
+
+            self.advance()?;
         }
 
-        Ok(_result)
+        Ok(digits)
+    }
+
+    /// Returns a span of the scanner's file between the given positions.
+    fn span(&self, start: Position, end: Position) -> Span {
+        Span::new(start, end).with_file(self.file)
+    }
+
+    /// Advances the scanner past the last character of a token or trivia, and returns its kind.
+    fn single<K>(&mut self, kind: K) -> Result<K, ScannerError> {
+        self.advance()?;
+
+        Ok(kind)
+    }
+}
+
+/// An iterator over the tokens of a scanner, see `Scanner::tokens`.
+#[derive(Debug)]
+pub struct Tokens<'a> {
+    scanner: &'a mut Scanner,
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Result<Token, ScannerError>;
+
+    /// Scans the next token, see `Scanner::next_token`.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scanner.next_token().transpose()
     }
 }
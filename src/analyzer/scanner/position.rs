@@ -1,14 +1,22 @@
 use std::fmt;
+use unicode_width::UnicodeWidthChar;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position {
+    /// Byte offset into the content.
     pub index: usize,
+    /// Zero-based line number.
     pub line: usize,
+    /// Zero-based column, counted in characters.
     pub column: usize,
+    /// Zero-based column, counted in UTF-16 code units as editor protocols expect.
+    pub utf16_column: usize,
 }
 
 impl Position {
     /// Returns a new position structure using the given arguments.
+    /// The UTF-16 column is the same as the column, which holds as long as the line before it has
+    /// no characters outside the Basic Multilingual Plane.
     ///
     /// # Examples
     /// ```
@@ -17,32 +25,23 @@ impl Position {
     /// assert_eq!(pos.index, 1);
     /// assert_eq!(pos.line, 2);
     /// assert_eq!(pos.column, 3);
+    /// assert_eq!(pos.utf16_column, 3);
     /// ```
     pub fn new(index: usize, line: usize, column: usize) -> Self {
         Position {
             index,
             line,
             column,
+            utf16_column: column,
         }
     }
 
-    /// Returns a new position structure with all values being zero.
-    ///
-    /// # Examples
-    /// ```
-    /// let pos = scrawlc::Position::default();
-    ///
-    /// assert_eq!(pos.index, 0);
-    /// assert_eq!(pos.line, 0);
-    /// assert_eq!(pos.column, 0);
-    /// ```
-    pub fn default() -> Self {
-        Position::new(0, 0, 0)
-    }
-
     /// Advances the position's values upon the `char` parameter.
+    /// The index is advanced by the UTF-8 length of the character.
     /// If it is a new line, it will update line and reset column;
     /// if it is another character, it will keep line as same and update column.
+    /// A carriage return ends a line together with the line feed after it, and a byte order mark
+    /// (`U+FEFF`) is invisible, so neither updates column.
     ///
     /// # Examples
     /// ```
@@ -59,17 +58,85 @@ impl Position {
     /// assert_eq!(pos.index, 3);
     /// assert_eq!(pos.line, 1);
     /// assert_eq!(pos.column, 0);
+    ///
+    /// pos.advance('é');
+    ///
+    /// assert_eq!(pos.index, 5);
+    /// assert_eq!(pos.column, 1);
+    ///
+    /// pos.advance('\u{FEFF}');
+    ///
+    /// assert_eq!(pos.index, 8);
+    /// assert_eq!(pos.column, 1);
+    ///
+    /// pos.advance('😀');
+    /// pos.advance('\r');
+    ///
+    /// assert_eq!(pos.column, 2);
+    /// assert_eq!(pos.utf16_column, 3);
+    ///
+    /// pos.advance('\n');
+    ///
+    /// assert_eq!(pos.line, 2);
+    /// assert_eq!(pos.utf16_column, 0);
     /// ```
     pub fn advance(&mut self, char: char) {
-        self.index += 1;
+        self.index += char.len_utf8();
 
-        if char == '\n' {
-            self.line += 1;
-            self.column = 0;
-        } else {
-            self.column += 1;
+        match char {
+            '\n' => {
+                self.line += 1;
+                self.column = 0;
+                self.utf16_column = 0;
+            }
+            '\r' | '\u{FEFF}' => {}
+            _ => {
+                self.column += 1;
+                self.utf16_column += char.len_utf16();
+            }
         }
     }
+
+    /// Returns the zero-based column as it is displayed, i.e. where a caret pointing at the
+    /// position has to be drawn.
+    /// Tabs advance to the next multiple of `tab_width`, and wide characters take two columns.
+    /// The source must be the content the position refers to.
+    ///
+    /// # Examples
+    /// ```
+    /// let source = "a\n\tb\n변수 c";
+    ///
+    /// assert_eq!(scrawlc::Position::new(3, 1, 1).display_column(source, 4), 4);
+    /// assert_eq!(scrawlc::Position::new(3, 1, 1).display_column(source, 8), 8);
+    /// assert_eq!(scrawlc::Position::new(12, 2, 3).display_column(source, 4), 5);
+    /// ```
+    pub fn display_column(&self, source: &str, tab_width: usize) -> usize {
+        let end = self.index.min(source.len());
+        let start = source[..end].rfind('\n').map_or(0, |index| index + 1);
+
+        source[start..end]
+            .chars()
+            .fold(0, |column, char| match char {
+                '\t' if tab_width > 0 => (column / tab_width + 1) * tab_width,
+                _ => column + char.width().unwrap_or(0),
+            })
+    }
+}
+
+impl Default for Position {
+    /// Returns a new position structure with all values being zero.
+    ///
+    /// # Examples
+    /// ```
+    /// let pos = scrawlc::Position::default();
+    ///
+    /// assert_eq!(pos.index, 0);
+    /// assert_eq!(pos.line, 0);
+    /// assert_eq!(pos.column, 0);
+    /// ```
+    fn default() -> Self {
+        Position::new(0, 0, 0)
+    }
 }
 
 impl fmt::Display for Position {
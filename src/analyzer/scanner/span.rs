@@ -0,0 +1,111 @@
+use crate::{analyzer::scanner::position::Position, source_map::FileId};
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+    /// The file the span refers to, see `SourceMap`, or `FileId::NONE`.
+    pub file: FileId,
+}
+
+impl Span {
+    /// Returns a new span structure using the given arguments, referring to no file.
+    /// The start position is inclusive while the end position is exclusive.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(0, 0, 0), scrawlc::Position::new(2, 0, 2));
+    ///
+    /// assert_eq!(span.start, scrawlc::Position::new(0, 0, 0));
+    /// assert_eq!(span.end, scrawlc::Position::new(2, 0, 2));
+    /// ```
+    pub fn new(start: Position, end: Position) -> Self {
+        Span {
+            start,
+            end,
+            file: FileId::default(),
+        }
+    }
+
+    /// Returns the span referring to the given file.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "import IO;");
+    /// let span = scrawlc::Span::default().with_file(file);
+    ///
+    /// assert_eq!(span.file, file);
+    /// ```
+    pub fn with_file(mut self, file: FileId) -> Self {
+        self.file = file;
+
+        self
+    }
+
+    /// Returns the number of bytes covered by the span.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(1, 0, 1), scrawlc::Position::new(4, 0, 4));
+    ///
+    /// assert_eq!(span.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.end.index - self.start.index
+    }
+
+    /// Returns `true` if the span does not cover any byte.
+    ///
+    /// # Examples
+    /// ```
+    /// let pos = scrawlc::Position::new(3, 0, 3);
+    ///
+    /// assert!(scrawlc::Span::new(pos, pos).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the smallest span covering both spans.
+    ///
+    /// # Examples
+    /// ```
+    /// let a = scrawlc::Span::new(scrawlc::Position::new(0, 0, 0), scrawlc::Position::new(2, 0, 2));
+    /// let b = scrawlc::Span::new(scrawlc::Position::new(5, 0, 5), scrawlc::Position::new(7, 0, 7));
+    ///
+    /// assert_eq!(a.to(&b), scrawlc::Span::new(a.start, b.end));
+    /// ```
+    pub fn to(&self, other: &Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end)).with_file(self.file)
+    }
+
+    /// Returns the part of the given source that is covered by the span.
+    /// The source must be the content the span was scanned from.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(7, 0, 7), scrawlc::Position::new(9, 0, 9));
+    ///
+    /// assert_eq!(span.slice("import IO;"), "IO");
+    /// ```
+    pub fn slice<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start.index..self.end.index]
+    }
+}
+
+impl fmt::Display for Span {
+    /// Formats a string with the start and end positions.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(7, 0, 7), scrawlc::Position::new(9, 0, 9));
+    ///
+    /// assert_eq!(span.to_string(), "1:8-1:10");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
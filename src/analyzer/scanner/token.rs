@@ -1,12 +1,22 @@
-use crate::analyzer::scanner::position::Position;
+mod kind;
+mod literal;
+
+pub use kind::{TokenKind, KEYWORDS};
+pub use literal::{Literal, NumberSuffix, StringStyle};
+
+use crate::analyzer::scanner::{position::Position, span::Span, trivia::Trivia};
 
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Token {
-    name: String,
-    value: String,
-    position: Position,
+    kind: TokenKind,
+    literal: Option<Literal>,
+    raw: Option<String>,
+    style: Option<StringStyle>,
+    span: Span,
+    leading_trivia: Vec<Trivia>,
+    trailing_trivia: Vec<Trivia>,
 }
 
 impl Token {
@@ -14,79 +24,301 @@ impl Token {
     ///
     /// # Examples
     /// ```
-    /// let pos = scrawlc::Position::default();
-    /// let tok = scrawlc::Token::new("exemplum", "exemplum", &pos);
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Identifier, Some("exemplum"), &span);
     ///
-    /// assert_eq!(tok.name(), "exemplum");
-    /// assert_eq!(tok.value(), "exemplum");
-    /// assert_eq!(tok.position(), &pos);
+    /// assert_eq!(tok.kind(), scrawlc::TokenKind::Identifier);
+    /// assert_eq!(tok.value(), Some("exemplum"));
+    /// assert_eq!(tok.span(), &span);
     /// ```
-    pub fn new(name: &str, value: &str, position: &Position) -> Self {
+    pub fn new(kind: TokenKind, value: Option<&str>, span: &Span) -> Self {
         Token {
-            name: name.to_string(),
-            value: value.to_string(),
-            position: position.clone(),
+            kind,
+            literal: value.map(|value| Literal::Text(value.to_string())),
+            raw: None,
+            style: None,
+            span: *span,
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
         }
     }
 
-    /// Returns the token name.
+    /// Returns the token with the given literal payload, replacing the value it was created with.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Character, None, &span)
+    ///     .with_literal(scrawlc::Literal::Character('c'));
+    ///
+    /// assert_eq!(tok.literal(), Some(&scrawlc::Literal::Character('c')));
+    /// assert_eq!(tok.character(), Some('c'));
+    /// ```
+    pub fn with_literal(mut self, literal: Literal) -> Self {
+        self.literal = Some(literal);
+
+        self
+    }
+
+    /// Returns the token with the given raw source text attached.
+    /// Literals whose value differs from their source, e.g. strings with escapes, keep both.
     ///
     /// # Examples
     /// ```
-    /// let pos = scrawlc::Position::default();
-    /// let tok = scrawlc::Token::new("exemplum", "exemplum", &pos);
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::String, Some("a\tb"), &span).with_raw("\"a\\tb\"");
     ///
-    /// assert_eq!(tok.name(), "exemplum");
+    /// assert_eq!(tok.value(), Some("a\tb"));
+    /// assert_eq!(tok.raw(), Some("\"a\\tb\""));
     /// ```
-    pub fn name(&self) -> &String {
-        &self.name
+    pub fn with_raw(mut self, raw: &str) -> Self {
+        self.raw = Some(raw.to_string());
+
+        self
+    }
+
+    /// Returns the token with the given string style attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::String, Some("a\\b"), &span)
+    ///     .with_style(scrawlc::StringStyle::Raw(0));
+    ///
+    /// assert_eq!(tok.style(), Some(scrawlc::StringStyle::Raw(0)));
+    /// ```
+    pub fn with_style(mut self, style: StringStyle) -> Self {
+        self.style = Some(style);
+
+        self
+    }
+
+    /// Returns the token with the given trivia attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(1, 0, 1), scrawlc::Position::new(2, 0, 2));
+    /// let space = scrawlc::Span::new(scrawlc::Position::new(0, 0, 0), span.start);
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Semicolon, None, &span)
+    ///     .with_trivia(vec![scrawlc::Trivia::new(scrawlc::TriviaKind::Whitespace, &space)], Vec::new());
+    ///
+    /// assert_eq!(tok.leading_trivia().len(), 1);
+    /// assert!(tok.trailing_trivia().is_empty());
+    /// ```
+    pub fn with_trivia(mut self, leading: Vec<Trivia>, trailing: Vec<Trivia>) -> Self {
+        self.leading_trivia = leading;
+        self.trailing_trivia = trailing;
+
+        self
     }
 
-    /// Returns the token value.
+    /// Returns the token kind.
     ///
     /// # Examples
     /// ```
-    /// let pos = scrawlc::Position::default();
-    /// let tok = scrawlc::Token::new("exemplum", "exemplum", &pos);
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Semicolon, None, &span);
     ///
-    /// assert_eq!(tok.value(), "exemplum");
+    /// assert_eq!(tok.kind(), scrawlc::TokenKind::Semicolon);
     /// ```
-    pub fn value(&self) -> &String {
-        &self.value
+    pub fn kind(&self) -> TokenKind {
+        self.kind
     }
 
-    /// Returns the token position.
+    /// Returns the literal payload of the token, if it carries one.
     ///
     /// # Examples
     /// ```
-    /// let pos = scrawlc::Position::default();
-    /// let tok = scrawlc::Token::new("exemplum", "exemplum", &pos);
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Identifier, Some("exemplum"), &span);
     ///
-    /// assert_eq!(tok.position(), &pos);
+    /// assert_eq!(tok.literal(), Some(&scrawlc::Literal::Text("exemplum".to_string())));
+    /// ```
+    pub fn literal(&self) -> Option<&Literal> {
+        self.literal.as_ref()
+    }
+
+    /// Returns the token value, if the token carries a textual payload.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let mut tok = scrawlc::Token::new(scrawlc::TokenKind::Identifier, Some("exemplum"), &span);
+    ///
+    /// assert_eq!(tok.value(), Some("exemplum"));
+    ///
+    /// tok = scrawlc::Token::new(scrawlc::TokenKind::Semicolon, None, &span);
+    ///
+    /// assert_eq!(tok.value(), None);
+    /// ```
+    pub fn value(&self) -> Option<&str> {
+        match &self.literal {
+            Some(Literal::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the character of a character literal.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("'\\n'").unwrap();
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[0].character(), Some('\n'));
+    /// assert_eq!(tokens[0].value(), None);
+    /// ```
+    pub fn character(&self) -> Option<char> {
+        match self.literal {
+            Some(Literal::Character(character)) => Some(character),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw source text of the token, if it was attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("\"say \\\"hi\\\"\"").unwrap();
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[0].value(), Some("say \"hi\""));
+    /// assert_eq!(tokens[0].raw(), Some("\"say \\\"hi\\\"\""));
+    /// ```
+    pub fn raw(&self) -> Option<&str> {
+        self.raw.as_deref()
+    }
+
+    /// Returns the form a string literal was written in.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("r#\"C:\\temp\"# \"\\n\"").unwrap();
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[0].style(), Some(scrawlc::StringStyle::Raw(1)));
+    /// assert_eq!(tokens[0].value(), Some("C:\\temp"));
+    /// assert_eq!(tokens[1].style(), Some(scrawlc::StringStyle::Quoted));
+    /// ```
+    pub fn style(&self) -> Option<StringStyle> {
+        self.style
+    }
+
+    /// Returns the token span.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Semicolon, None, &span);
+    ///
+    /// assert_eq!(tok.span(), &span);
+    /// ```
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Returns the trivia before the token.
+    /// Trivia is only kept when scanning in lossless mode.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a\n  b").unwrap();
+    /// scanner.set_lossless(true);
+    ///
+    /// let tokens = scanner.scan().unwrap();
+    /// let kinds: Vec<_> = tokens[1].leading_trivia().iter().map(|t| t.kind()).collect();
+    ///
+    /// assert_eq!(kinds, vec![scrawlc::TriviaKind::Newline, scrawlc::TriviaKind::Whitespace]);
+    /// ```
+    pub fn leading_trivia(&self) -> &[Trivia] {
+        &self.leading_trivia
+    }
+
+    /// Returns the trivia after the token on the same line.
+    /// Trivia is only kept when scanning in lossless mode.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a // note\nb").unwrap();
+    /// scanner.set_lossless(true);
+    ///
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[0].trailing_trivia().len(), 2);
+    /// ```
+    pub fn trailing_trivia(&self) -> &[Trivia] {
+        &self.trailing_trivia
+    }
+
+    /// Returns the span of the token including its trivia.
+    ///
+    /// # Examples
+    /// ```
+    /// let content = "  a; // note\n";
+    /// let mut scanner = scrawlc::Scanner::new(content).unwrap();
+    /// scanner.set_lossless(true);
+    ///
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[0].full_span().slice(content), "  a");
+    /// assert_eq!(tokens[1].full_span().slice(content), "; // note");
+    /// ```
+    pub fn full_span(&self) -> Span {
+        let start = self.leading_trivia.first().map_or(&self.span, |t| t.span());
+        let end = self.trailing_trivia.last().map_or(&self.span, |t| t.span());
+
+        start.to(end)
+    }
+
+    /// Returns the token position, which is the start of the token span.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let tok = scrawlc::Token::new(scrawlc::TokenKind::Semicolon, None, &span);
+    ///
+    /// assert_eq!(tok.position(), &span.start);
     /// ```
     pub fn position(&self) -> &Position {
-        &self.position
+        &self.span.start
+    }
+
+    /// Returns the source text of the token.
+    /// The source must be the content the token was scanned from.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("print(\"hi\");").unwrap();
+    /// let tokens = scanner.scan().unwrap();
+    ///
+    /// assert_eq!(tokens[2].text(scanner.content()), "\"hi\"");
+    /// ```
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        self.span.slice(source)
     }
 }
 
 impl fmt::Display for Token {
     /// Formats a string with the token's attributes.
+    /// The raw source text is preferred over the value, so escaped characters stay readable.
     ///
     /// ```
-    /// let pos = scrawlc::Position::default();
-    /// let mut tok = scrawlc::Token::new("exemplum", "exemplum", &pos);
+    /// let span = scrawlc::Span::default();
+    /// let mut tok = scrawlc::Token::new(scrawlc::TokenKind::Semicolon, None, &span);
     ///
-    /// assert_eq!(tok.to_string(), "<exemplum>@1:1");
+    /// assert_eq!(tok.to_string(), "<;>@1:1");
     ///
-    /// tok = scrawlc::Token::new("exemplum", "dissimilis", &pos);
+    /// tok = scrawlc::Token::new(scrawlc::TokenKind::Identifier, Some("exemplum"), &span);
     ///
-    /// assert_eq!(tok.to_string(), "<exemplum>@1:1 = dissimilis");
+    /// assert_eq!(tok.to_string(), "<identifier>@1:1 = exemplum");
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.name == self.value {
-            write!(f, "<{}>@{}", self.value, self.position)
-        } else {
-            write!(f, "<{}>@{} = {}", self.name, self.position, self.value)
+        match (&self.raw, &self.literal) {
+            (Some(raw), _) => write!(f, "<{}>@{} = {}", self.kind, self.span.start, raw),
+            (None, Some(literal)) => {
+                write!(f, "<{}>@{} = {}", self.kind, self.span.start, literal)
+            }
+            (None, None) => write!(f, "<{}>@{}", self.kind, self.span.start),
         }
     }
 }
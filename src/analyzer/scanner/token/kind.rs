@@ -0,0 +1,312 @@
+use std::fmt;
+
+/// Words that are recognized as keywords instead of identifiers.
+pub const KEYWORDS: &[(&str, TokenKind)] = &[
+    ("as", TokenKind::As),
+    ("break", TokenKind::Break),
+    ("class", TokenKind::Class),
+    ("const", TokenKind::Const),
+    ("continue", TokenKind::Continue),
+    ("else", TokenKind::Else),
+    ("false", TokenKind::False),
+    ("fn", TokenKind::Fn),
+    ("for", TokenKind::For),
+    ("if", TokenKind::If),
+    ("import", TokenKind::Import),
+    ("in", TokenKind::In),
+    ("let", TokenKind::Let),
+    ("new", TokenKind::New),
+    ("null", TokenKind::Null),
+    ("return", TokenKind::Return),
+    ("true", TokenKind::True),
+    ("while", TokenKind::While),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Unsupported input, produced when scanning with recovery.
+    Error,
+    /// The end of the content, always the last token scanned.
+    Eof,
+
+    // Literals
+    Identifier,
+    Integer,
+    Float,
+    String,
+    Character,
+
+    // Interpolated strings, e.g. `"Hello, {name}!"`
+    /// The opening quote of a string with interpolations.
+    StringStart,
+    /// A piece of text in a string with interpolations.
+    StringFragment,
+    /// The `{` opening an interpolation in a string.
+    InterpolationOpen,
+    /// The `}` closing an interpolation in a string.
+    InterpolationClose,
+    /// The closing quote of a string with interpolations.
+    StringEnd,
+
+    // Comments
+    /// An outer documentation comment, `/// ...`.
+    DocComment,
+    /// An inner documentation comment, `//! ...`.
+    InnerDocComment,
+
+    // Keywords
+    As,
+    Break,
+    Class,
+    Const,
+    Continue,
+    Else,
+    False,
+    Fn,
+    For,
+    If,
+    Import,
+    In,
+    Let,
+    New,
+    Null,
+    Return,
+    True,
+    While,
+
+    // Punctuators
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Semicolon,
+    Comma,
+    Dot,
+    DotDot,
+    DotDotDot,
+    DotDotEqual,
+    At,
+    Hash,
+
+    // Operators
+    Equal,
+    EqualEqual,
+    FatArrow,
+    Plus,
+    PlusEqual,
+    PlusPlus,
+    Greater,
+    GreaterEqual,
+    GreaterGreater,
+    GreaterGreaterEqual,
+    Minus,
+    MinusEqual,
+    MinusMinus,
+    Arrow,
+    Less,
+    LessEqual,
+    LessLess,
+    LessLessEqual,
+    Star,
+    StarEqual,
+    StarStar,
+    StarStarEqual,
+    Bang,
+    BangEqual,
+    Slash,
+    SlashEqual,
+    Tilde,
+    Ampersand,
+    AmpersandEqual,
+    AmpersandAmpersand,
+    AmpersandAmpersandEqual,
+    Question,
+    QuestionQuestion,
+    QuestionQuestionEqual,
+    QuestionDot,
+    Pipe,
+    PipeEqual,
+    PipePipe,
+    PipePipeEqual,
+    Colon,
+    ColonColon,
+    Caret,
+    CaretEqual,
+    Percent,
+    PercentEqual,
+}
+
+impl TokenKind {
+    /// Returns the name of the token kind.
+    /// Punctuators and operators are named after their source text.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::TokenKind::Identifier.name(), "identifier");
+    /// assert_eq!(scrawlc::TokenKind::PlusEqual.name(), "+=");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenKind::Error => "error",
+            TokenKind::Eof => "end of file",
+            TokenKind::Identifier => "identifier",
+            TokenKind::Integer => "integer",
+            TokenKind::Float => "float",
+            TokenKind::String => "string",
+            TokenKind::Character => "character",
+            TokenKind::StringStart => "string start",
+            TokenKind::StringFragment => "string fragment",
+            TokenKind::InterpolationOpen => "interpolation open",
+            TokenKind::InterpolationClose => "interpolation close",
+            TokenKind::StringEnd => "string end",
+            TokenKind::DocComment => "doc comment",
+            TokenKind::InnerDocComment => "inner doc comment",
+            TokenKind::As => "as",
+            TokenKind::Break => "break",
+            TokenKind::Class => "class",
+            TokenKind::Const => "const",
+            TokenKind::Continue => "continue",
+            TokenKind::Else => "else",
+            TokenKind::False => "false",
+            TokenKind::Fn => "fn",
+            TokenKind::For => "for",
+            TokenKind::If => "if",
+            TokenKind::Import => "import",
+            TokenKind::In => "in",
+            TokenKind::Let => "let",
+            TokenKind::New => "new",
+            TokenKind::Null => "null",
+            TokenKind::Return => "return",
+            TokenKind::True => "true",
+            TokenKind::While => "while",
+            TokenKind::LeftParen => "(",
+            TokenKind::RightParen => ")",
+            TokenKind::LeftBrace => "{",
+            TokenKind::RightBrace => "}",
+            TokenKind::LeftBracket => "[",
+            TokenKind::RightBracket => "]",
+            TokenKind::Semicolon => ";",
+            TokenKind::Comma => ",",
+            TokenKind::Dot => ".",
+            TokenKind::DotDot => "..",
+            TokenKind::DotDotDot => "...",
+            TokenKind::DotDotEqual => "..=",
+            TokenKind::At => "@",
+            TokenKind::Hash => "#",
+            TokenKind::Equal => "=",
+            TokenKind::EqualEqual => "==",
+            TokenKind::FatArrow => "=>",
+            TokenKind::Plus => "+",
+            TokenKind::PlusEqual => "+=",
+            TokenKind::PlusPlus => "++",
+            TokenKind::Greater => ">",
+            TokenKind::GreaterEqual => ">=",
+            TokenKind::GreaterGreater => ">>",
+            TokenKind::GreaterGreaterEqual => ">>=",
+            TokenKind::Minus => "-",
+            TokenKind::MinusEqual => "-=",
+            TokenKind::MinusMinus => "--",
+            TokenKind::Arrow => "->",
+            TokenKind::Less => "<",
+            TokenKind::LessEqual => "<=",
+            TokenKind::LessLess => "<<",
+            TokenKind::LessLessEqual => "<<=",
+            TokenKind::Star => "*",
+            TokenKind::StarEqual => "*=",
+            TokenKind::StarStar => "**",
+            TokenKind::StarStarEqual => "**=",
+            TokenKind::Bang => "!",
+            TokenKind::BangEqual => "!=",
+            TokenKind::Slash => "/",
+            TokenKind::SlashEqual => "/=",
+            TokenKind::Tilde => "~",
+            TokenKind::Ampersand => "&",
+            TokenKind::AmpersandEqual => "&=",
+            TokenKind::AmpersandAmpersand => "&&",
+            TokenKind::AmpersandAmpersandEqual => "&&=",
+            TokenKind::Question => "?",
+            TokenKind::QuestionQuestion => "??",
+            TokenKind::QuestionQuestionEqual => "??=",
+            TokenKind::QuestionDot => "?.",
+            TokenKind::Pipe => "|",
+            TokenKind::PipeEqual => "|=",
+            TokenKind::PipePipe => "||",
+            TokenKind::PipePipeEqual => "||=",
+            TokenKind::Colon => ":",
+            TokenKind::ColonColon => "::",
+            TokenKind::Caret => "^",
+            TokenKind::CaretEqual => "^=",
+            TokenKind::Percent => "%",
+            TokenKind::PercentEqual => "%=",
+        }
+    }
+
+    /// Returns the keyword kind matching the given word, if it is reserved.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::TokenKind::keyword("import"), Some(scrawlc::TokenKind::Import));
+    /// assert_eq!(scrawlc::TokenKind::keyword("print"), None);
+    /// ```
+    pub fn keyword(word: &str) -> Option<TokenKind> {
+        KEYWORDS
+            .iter()
+            .find(|(keyword, _)| *keyword == word)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Returns `true` if the given word is reserved and cannot be used as an identifier.
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(scrawlc::TokenKind::is_reserved("new"));
+    /// assert!(!scrawlc::TokenKind::is_reserved("file"));
+    /// ```
+    pub fn is_reserved(word: &str) -> bool {
+        TokenKind::keyword(word).is_some()
+    }
+
+    /// Returns `true` if the token kind is a keyword.
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(scrawlc::TokenKind::Return.is_keyword());
+    /// assert!(!scrawlc::TokenKind::Identifier.is_keyword());
+    /// ```
+    pub fn is_keyword(&self) -> bool {
+        KEYWORDS.iter().any(|(_, kind)| kind == self)
+    }
+
+    /// Returns `true` if tokens of this kind carry a literal payload.
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(scrawlc::TokenKind::String.is_literal());
+    /// assert!(!scrawlc::TokenKind::Semicolon.is_literal());
+    /// ```
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Identifier
+                | TokenKind::Integer
+                | TokenKind::Float
+                | TokenKind::String
+                | TokenKind::Character
+                | TokenKind::StringFragment
+        )
+    }
+}
+
+impl fmt::Display for TokenKind {
+    /// Formats a string with the token kind's name.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::TokenKind::ColonColon.to_string(), "::");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
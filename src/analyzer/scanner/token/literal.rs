@@ -0,0 +1,164 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberSuffix {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+}
+
+impl NumberSuffix {
+    /// Returns the number suffix written as the given text.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::NumberSuffix::from_suffix("u8"), Some(scrawlc::NumberSuffix::U8));
+    /// assert_eq!(scrawlc::NumberSuffix::from_suffix("u7"), None);
+    /// ```
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "i8" => Some(NumberSuffix::I8),
+            "i16" => Some(NumberSuffix::I16),
+            "i32" => Some(NumberSuffix::I32),
+            "i64" => Some(NumberSuffix::I64),
+            "i128" => Some(NumberSuffix::I128),
+            "u8" => Some(NumberSuffix::U8),
+            "u16" => Some(NumberSuffix::U16),
+            "u32" => Some(NumberSuffix::U32),
+            "u64" => Some(NumberSuffix::U64),
+            "u128" => Some(NumberSuffix::U128),
+            "f32" => Some(NumberSuffix::F32),
+            "f64" => Some(NumberSuffix::F64),
+            _ => None,
+        }
+    }
+
+    /// Returns the suffix as it is written in the source.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::NumberSuffix::I128.as_str(), "i128");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NumberSuffix::I8 => "i8",
+            NumberSuffix::I16 => "i16",
+            NumberSuffix::I32 => "i32",
+            NumberSuffix::I64 => "i64",
+            NumberSuffix::I128 => "i128",
+            NumberSuffix::U8 => "u8",
+            NumberSuffix::U16 => "u16",
+            NumberSuffix::U32 => "u32",
+            NumberSuffix::U64 => "u64",
+            NumberSuffix::U128 => "u128",
+            NumberSuffix::F32 => "f32",
+            NumberSuffix::F64 => "f64",
+        }
+    }
+
+    /// Returns `true` if the suffix denotes a floating-point type.
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(scrawlc::NumberSuffix::F32.is_float());
+    /// assert!(!scrawlc::NumberSuffix::U32.is_float());
+    /// ```
+    pub fn is_float(&self) -> bool {
+        matches!(self, NumberSuffix::F32 | NumberSuffix::F64)
+    }
+
+    /// Returns the largest integer that can be written with the suffix,
+    /// or `None` for floating-point types.
+    ///
+    /// Literals have no sign, so for signed types this is the magnitude of the minimum, which
+    /// lets `-128i8` be written. Whether a positive literal fits is left to the parser.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::NumberSuffix::U8.max_integer(), Some(255));
+    /// assert_eq!(scrawlc::NumberSuffix::I8.max_integer(), Some(128));
+    /// assert_eq!(scrawlc::NumberSuffix::F64.max_integer(), None);
+    /// ```
+    pub fn max_integer(&self) -> Option<u128> {
+        match self {
+            NumberSuffix::I8 => Some(i8::MIN.unsigned_abs() as u128),
+            NumberSuffix::I16 => Some(i16::MIN.unsigned_abs() as u128),
+            NumberSuffix::I32 => Some(i32::MIN.unsigned_abs() as u128),
+            NumberSuffix::I64 => Some(i64::MIN.unsigned_abs() as u128),
+            NumberSuffix::I128 => Some(i128::MIN.unsigned_abs()),
+            NumberSuffix::U8 => Some(u8::MAX as u128),
+            NumberSuffix::U16 => Some(u16::MAX as u128),
+            NumberSuffix::U32 => Some(u32::MAX as u128),
+            NumberSuffix::U64 => Some(u64::MAX as u128),
+            NumberSuffix::U128 => Some(u128::MAX),
+            NumberSuffix::F32 | NumberSuffix::F64 => None,
+        }
+    }
+}
+
+impl fmt::Display for NumberSuffix {
+    /// Formats a string with the suffix as it is written in the source.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::NumberSuffix::F32.to_string(), "f32");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The form a string literal was written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringStyle {
+    /// A `"..."` string, whose escapes are processed.
+    Quoted,
+    /// A `r"..."` or `r#"..."#` string, whose content is kept as written.
+    /// Holds the number of `#`s around the quotes.
+    Raw(usize),
+    /// A `"""..."""` string spanning multiple lines, whose common indentation is stripped.
+    Multiline,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// Names of identifiers and the values of string literals.
+    Text(String),
+    /// The value of a character literal.
+    Character(char),
+    /// The value and the optional type suffix of an integer literal.
+    Integer(u128, Option<NumberSuffix>),
+    /// The value and the optional type suffix of a floating-point literal.
+    Float(f64, Option<NumberSuffix>),
+}
+
+impl fmt::Display for Literal {
+    /// Formats a string with the literal's value.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Literal::Text("exemplum".to_string()).to_string(), "exemplum");
+    /// assert_eq!(scrawlc::Literal::Character('e').to_string(), "e");
+    /// assert_eq!(scrawlc::Literal::Integer(255, Some(scrawlc::NumberSuffix::U8)).to_string(), "255u8");
+    /// assert_eq!(scrawlc::Literal::Float(2.5, None).to_string(), "2.5");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Text(text) => write!(f, "{}", text),
+            Literal::Character(character) => write!(f, "{}", character),
+            Literal::Integer(value, Some(suffix)) => write!(f, "{}{}", value, suffix),
+            Literal::Integer(value, None) => write!(f, "{}", value),
+            Literal::Float(value, Some(suffix)) => write!(f, "{}{}", value, suffix),
+            Literal::Float(value, None) => write!(f, "{}", value),
+        }
+    }
+}
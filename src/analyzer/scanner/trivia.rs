@@ -0,0 +1,82 @@
+use crate::analyzer::scanner::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    /// Spaces, tabs and carriage returns.
+    Whitespace,
+    /// A single line feed.
+    Newline,
+    /// A `//` comment, without the line feed that ends it.
+    LineComment,
+    /// A `/* ... */` comment, including nested comments.
+    BlockComment,
+    /// A UTF-8 byte order mark at the start of the content.
+    ByteOrderMark,
+    /// A `#!` line at the start of the content, without the line feed that ends it.
+    Shebang,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trivia {
+    kind: TriviaKind,
+    span: Span,
+}
+
+impl Trivia {
+    /// Returns a new trivia structure using the given arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let trivia = scrawlc::Trivia::new(scrawlc::TriviaKind::Whitespace, &span);
+    ///
+    /// assert_eq!(trivia.kind(), scrawlc::TriviaKind::Whitespace);
+    /// assert_eq!(trivia.span(), &span);
+    /// ```
+    pub fn new(kind: TriviaKind, span: &Span) -> Self {
+        Trivia { kind, span: *span }
+    }
+
+    /// Returns the trivia kind.
+    ///
+    /// # Examples
+    /// ```
+    /// let trivia = scrawlc::Trivia::new(scrawlc::TriviaKind::Newline, &scrawlc::Span::default());
+    ///
+    /// assert_eq!(trivia.kind(), scrawlc::TriviaKind::Newline);
+    /// ```
+    pub fn kind(&self) -> TriviaKind {
+        self.kind
+    }
+
+    /// Returns the trivia span.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let trivia = scrawlc::Trivia::new(scrawlc::TriviaKind::Newline, &span);
+    ///
+    /// assert_eq!(trivia.span(), &span);
+    /// ```
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Returns the source text of the trivia.
+    /// The source must be the content the trivia was scanned from.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut scanner = scrawlc::Scanner::new("a // note").unwrap();
+    /// scanner.set_lossless(true);
+    ///
+    /// let tokens = scanner.scan().unwrap();
+    /// let trivia = tokens[0].trailing_trivia();
+    ///
+    /// assert_eq!(trivia[1].kind(), scrawlc::TriviaKind::LineComment);
+    /// assert_eq!(trivia[1].text(scanner.content()), "// note");
+    /// ```
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        self.span.slice(source)
+    }
+}
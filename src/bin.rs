@@ -1,101 +1,307 @@
 use colored::Colorize;
-use scrawlc;
-use std::{env, fs, path, process};
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path, process,
+};
 
 const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-fn print_help() {
-    println!("{} ({})", "scrawlc".green(), PKG_VERSION.bold());
+/// Returns the usage of the command.
+fn help() -> String {
+    [
+        format!("{} ({})", "scrawlc".green(), PKG_VERSION.bold()),
+        format!(
+            "{} scrawlc {} {} {}",
+            "usage:".yellow().bold(),
+            "[--error-format=human|json]".purple(),
+            "<input file>".purple(),
+            "<output file>".purple()
+        ),
+        format!(
+            "{} {} {}",
+            "lints:".yellow().bold(),
+            "-A|-W|-D <lint>".purple(),
+            "(-W help lists the lints, -D warnings denies all warnings)".bright_black()
+        ),
+        format!(
+            "{} scrawlc fix {}",
+            "usage:".yellow().bold(),
+            "<file>".purple()
+        ),
+        format!(
+            "{} scrawlc --explain {}",
+            "usage:".yellow().bold(),
+            "<code>".purple()
+        ),
+    ]
+    .join("\n")
+}
+
+/// Prints the usage on standard error, which is kept free of anything but diagnostics in JSON
+/// mode, and exits with a failure.
+fn exit_with_usage() -> ! {
+    eprintln!("{}", help());
+
+    process::exit(1)
+}
+
+fn print_lints() {
+    println!("{}", "lints:".yellow().bold());
+
+    for lint in scrawlc::LINTS {
+        println!(
+            "    {:<26} {:<6} {}",
+            lint.name.purple(),
+            lint.default_level.to_string(),
+            lint.description
+        );
+    }
 
     println!(
-        "{} scrawlc {} {}",
-        "usage:".yellow().bold(),
-        "<input file>".purple(),
-        "<output file>".purple()
+        "    {:<33} all lints reported as warnings",
+        scrawlc::WARNINGS.purple()
     );
 }
 
-fn print_scanner_error(error: &scrawlc::ScannerError) {
-    print!("{} ", "error:".red().bold());
+/// Returns the lint level set by a `-A`, `-W` or `-D` flag, with or without the lint name attached.
+fn lint_level_flag(arg: &str) -> Option<scrawlc::Level> {
+    match arg.get(..2) {
+        Some("-A") => Some(scrawlc::Level::Allow),
+        Some("-W") => Some(scrawlc::Level::Warn),
+        Some("-D") => Some(scrawlc::Level::Deny),
+        _ => None,
+    }
+}
 
-    match error {
-        scrawlc::ScannerError::EndOfContent(position) => {
-            println!(
-                "cannot access {}, end of content",
-                position.to_string().purple()
-            )
+/// Returns `true` if the given `--error-format` value selects JSON, `false` for human output.
+fn is_json_error_format(format: &str) -> bool {
+    match format {
+        "human" => false,
+        "json" => true,
+        _ => {
+            eprintln!(
+                "{} unknown error format {}, expected human or json",
+                "error:".red().bold(),
+                format.purple()
+            );
+
+            process::exit(1)
         }
-        scrawlc::ScannerError::UnsupportedCharacter(character) => {
-            println!(
-                "{} is an unsupported character",
-                character.to_string().purple()
-            )
+    }
+}
+
+fn print_explanation(code: &str) {
+    match scrawlc::explain(code) {
+        Some(explanation) => print!("{}", explanation),
+        None => {
+            eprintln!(
+                "{} {} is not a known diagnostic code",
+                "error:".red().bold(),
+                code.purple()
+            );
+
+            process::exit(1)
+        }
+    }
+}
+
+/// How diagnostics are printed.
+enum Emitter<'a> {
+    /// Rendered with the source lines they point at.
+    Human(scrawlc::Renderer<'a>),
+    /// One JSON object per line.
+    Json(&'a scrawlc::SourceMap),
+}
+
+impl Emitter<'_> {
+    fn emit(&self, diagnostic: &scrawlc::Diagnostic) {
+        match self {
+            Emitter::Human(renderer) => println!("{}", renderer.render(diagnostic)),
+            Emitter::Json(source_map) => println!("{}", diagnostic.to_json(source_map)),
+        }
+    }
+
+    fn is_json(&self) -> bool {
+        matches!(self, Emitter::Json(_))
+    }
+}
+
+fn read_file(file_path: &str) -> String {
+    match fs::read_to_string(file_path) {
+        Ok(content) => content,
+        Err(error) => {
+            if !path::PathBuf::from(file_path).is_file() {
+                eprintln!(
+                    "{} {} is not a file",
+                    "error:".red().bold(),
+                    file_path.purple()
+                );
+
+                process::exit(1)
+            } else {
+                eprintln!(
+                    "{} could not read {}; {}",
+                    "error:".red().bold(),
+                    file_path.purple(),
+                    error.to_string().bright_black().italic()
+                );
+
+                process::exit(1)
+            }
+        }
+    }
+}
+
+/// Applies the machine-applicable suggestions of the file's diagnostics to it, in place.
+fn fix(file_path: &str, lint_levels: &scrawlc::LintLevels, color: bool) {
+    let content = read_file(file_path);
+
+    let mut source_map = scrawlc::SourceMap::new();
+    let file = source_map.add_file(file_path, &content);
+    let renderer = scrawlc::Renderer::new(&source_map).with_color(color);
+
+    let mut scanner = match scrawlc::Scanner::new(&content) {
+        Ok(scanner) => scanner,
+        Err(error) => {
+            println!("{}", renderer.render(&error.diagnostic()));
+
+            process::exit(1)
         }
+    };
+
+    scanner.set_file(file);
+
+    let (tokens, errors) = scanner.scan_recovering();
+    let suggestions: Vec<_> = scrawlc::check_lints(&tokens, scanner.warnings(), lint_levels)
+        .into_iter()
+        .chain(errors.iter().map(|error| error.diagnostic()))
+        .flat_map(|diagnostic| diagnostic.suggestions().to_vec())
+        .collect();
+
+    let (fixed, conflicts) = scrawlc::apply_suggestions(&content, &suggestions);
+
+    for conflict in conflicts.iter() {
+        println!("{}", renderer.render(&conflict.diagnostic()));
+    }
+
+    if fixed == content {
+        println!(
+            "{} nothing to fix in {}",
+            "note:".green().bold(),
+            file_path.purple()
+        );
+
+        return;
+    }
+
+    if let Err(error) = fs::write(file_path, fixed) {
+        eprintln!(
+            "{} could not write {}; {}",
+            "error:".red().bold(),
+            file_path.purple(),
+            error.to_string().bright_black().italic()
+        );
+
+        process::exit(1)
     }
+
+    println!("{} {}", "fixed".green().bold(), file_path.purple());
 }
 
 fn main() {
     let args: Vec<_> = env::args().collect();
     let mut verbose: bool = false;
+    let mut json: bool = false;
+    let mut paths: Vec<&String> = Vec::new();
+    let mut lint_levels = scrawlc::LintLevels::new();
+    let color = io::stdout().is_terminal();
 
-    for arg in args.iter() {
+    colored::control::set_override(color);
+
+    let mut args_iter = args.iter().skip(1);
+
+    while let Some(arg) = args_iter.next() {
         if arg == "-v" || arg == "--verbose" {
             verbose = true;
         } else if arg == "-h" || arg == "--help" {
-            print_help();
+            println!("{}", help());
 
             process::exit(0);
-        }
-    }
+        } else if arg == "--explain" {
+            match args_iter.next() {
+                Some(code) => print_explanation(code),
+                None => exit_with_usage(),
+            }
 
-    if args.len() < 3 {
-        print_help();
+            process::exit(0);
+        } else if let Some(code) = arg.strip_prefix("--explain=") {
+            print_explanation(code);
 
-        process::exit(1)
-    }
+            process::exit(0);
+        } else if arg == "--error-format" {
+            match args_iter.next() {
+                Some(format) => json = is_json_error_format(format),
+                None => exit_with_usage(),
+            }
+        } else if let Some(format) = arg.strip_prefix("--error-format=") {
+            json = is_json_error_format(format);
+        } else if let Some(level) = lint_level_flag(arg) {
+            let name = match arg.get(2..).filter(|name| !name.is_empty()) {
+                Some(name) => name,
+                None => match args_iter.next() {
+                    Some(name) => name.as_str(),
+                    None => exit_with_usage(),
+                },
+            };
+            let name = name.replace('-', "_");
+
+            if name == "help" {
+                print_lints();
 
-    let input_content = match args.get(1) {
-        Some(file_path) => match fs::read_to_string(file_path) {
-            Ok(content) => content,
-            Err(error) => {
-                if !path::PathBuf::from(file_path).is_file() {
-                    println!(
-                        "{} {} is not a file",
-                        "error:".red().bold(),
-                        file_path.purple()
-                    );
-
-                    process::exit(1)
-                } else {
-                    println!(
-                        "{} could not read {}; {}",
-                        "error:".red().bold(),
-                        file_path.purple(),
-                        error.to_string().bright_black().italic()
-                    );
-
-                    process::exit(1)
-                }
+                process::exit(0);
+            } else if name != scrawlc::WARNINGS && scrawlc::find_lint(&name).is_none() {
+                eprintln!("{} unknown lint {}", "error:".red().bold(), name.purple());
+
+                process::exit(1)
             }
-        },
-        None => {
-            print_help();
+
+            lint_levels = lint_levels.with_level(&name, level);
+        } else if arg.starts_with('-') {
+            eprintln!("{} unknown flag {}", "error:".red().bold(), arg.purple());
 
             process::exit(1)
+        } else {
+            paths.push(arg);
         }
-    };
+    }
 
-    let output_file_path = match args.get(2) {
-        Some(arg) => path::PathBuf::from(arg),
-        None => {
-            print_help();
+    // Standard output only holds diagnostics in JSON mode, so there is no progress to report.
+    let verbose = verbose && !json;
 
-            process::exit(1)
+    if paths.first().is_some_and(|arg| *arg == "fix") {
+        match paths.get(1) {
+            Some(file_path) => fix(file_path, &lint_levels, color),
+            None => exit_with_usage(),
         }
+
+        return;
+    }
+
+    if paths.len() < 2 {
+        exit_with_usage()
+    }
+
+    let input_file_path = paths.first().map_or("", |arg| arg.as_str());
+    let input_content = read_file(input_file_path);
+
+    let output_file_path = match paths.get(1) {
+        Some(arg) => path::PathBuf::from(arg),
+        None => exit_with_usage(),
     };
 
     if !output_file_path.is_file() {
-        println!(
+        eprintln!(
             "{} {} is not a file",
             "error:".red().bold(),
             output_file_path
@@ -111,6 +317,14 @@ fn main() {
         print!("  {} Scanning ", "[1/1]".bright_white());
     }
 
+    let mut source_map = scrawlc::SourceMap::new();
+    let file = source_map.add_file(input_file_path, &input_content);
+    let emitter = if json {
+        Emitter::Json(&source_map)
+    } else {
+        Emitter::Human(scrawlc::Renderer::new(&source_map).with_color(color))
+    };
+
     let mut scanner = match scrawlc::Scanner::new(&input_content) {
         Ok(scanner) => scanner,
         Err(error) => {
@@ -118,35 +332,89 @@ fn main() {
                 println!("{}", "failed".red());
             }
 
-            print_scanner_error(&error);
+            emitter.emit(&error.diagnostic());
 
             process::exit(1)
         }
     };
 
-    let scan_result = match scanner.scan() {
-        Ok(scan_result) => scan_result,
-        Err(error) => {
-            if verbose {
-                println!("{}", "failed".red());
-            }
+    scanner.set_file(file);
 
-            print_scanner_error(&error);
+    let (scan_result, errors) = scanner.scan_recovering();
+    let diagnostics: Vec<_> = scrawlc::check_lints(&scan_result, scanner.warnings(), &lint_levels)
+        .into_iter()
+        .chain(errors.iter().map(|error| error.diagnostic()))
+        .collect();
+    let error_codes: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity() == scrawlc::Severity::Error)
+        .map(|diagnostic| diagnostic.code())
+        .collect();
 
+    if !error_codes.is_empty() {
+        if verbose {
+            println!("{}", "failed".red());
+        }
+
+        for diagnostic in diagnostics.iter() {
+            emitter.emit(diagnostic);
+        }
+
+        if emitter.is_json() {
             process::exit(1)
         }
-    };
+
+        println!(
+            "{} could not scan due to {} previous error{}",
+            "error:".red().bold(),
+            error_codes.len(),
+            if error_codes.len() == 1 { "" } else { "s" }
+        );
+
+        let mut codes: Vec<_> = error_codes.into_iter().flatten().collect();
+        codes.sort_unstable();
+        codes.dedup();
+
+        if codes.len() == 1 {
+            println!(
+                "For more information about this error, try `scrawlc --explain {}`.",
+                codes[0]
+            );
+        } else if codes.len() > 1 {
+            println!(
+                "Some errors have detailed explanations: {}.",
+                codes.join(", ")
+            );
+            println!(
+                "For more information about an error, try `scrawlc --explain {}`.",
+                codes[0]
+            );
+        }
+
+        process::exit(1)
+    }
 
     if verbose {
         println!("{}", "succeeded".green());
     }
 
+    for diagnostic in diagnostics.iter() {
+        emitter.emit(diagnostic);
+    }
+
     for (i, token) in scan_result.iter().enumerate() {
-        println!(
+        let line = format!(
             "    {}{} {}",
             i.to_string().bright_green(),
             ":".bright_green(),
             token
         );
+
+        // The tokens go to standard error in JSON mode, to keep one diagnostic per line of output.
+        if json {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
     }
 }
@@ -0,0 +1,910 @@
+use crate::{analyzer::Span, source_map::SourceMap};
+
+use colored::{ColoredString, Colorize};
+use serde_json::json;
+use std::{fmt, fmt::Write};
+use unicode_width::UnicodeWidthStr;
+
+/// Long-form explanations of the diagnostic codes, each with an example, by code.
+pub const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0001", include_str!("explanations/E0001.md")),
+    ("E0002", include_str!("explanations/E0002.md")),
+    ("E0003", include_str!("explanations/E0003.md")),
+    ("E0004", include_str!("explanations/E0004.md")),
+    ("E0005", include_str!("explanations/E0005.md")),
+    ("E0006", include_str!("explanations/E0006.md")),
+    ("E0007", include_str!("explanations/E0007.md")),
+    ("E0008", include_str!("explanations/E0008.md")),
+    ("E0009", include_str!("explanations/E0009.md")),
+    ("E0010", include_str!("explanations/E0010.md")),
+    ("W0001", include_str!("explanations/W0001.md")),
+    ("W0002", include_str!("explanations/W0002.md")),
+    ("W0003", include_str!("explanations/W0003.md")),
+    ("W0004", include_str!("explanations/W0004.md")),
+];
+
+/// Returns the long-form explanation of the given diagnostic code, or `None` if the code is
+/// unknown.
+///
+/// # Examples
+/// ```
+/// assert!(scrawlc::explain("E0002").unwrap().contains("Erroneous code example"));
+/// assert_eq!(scrawlc::explain("E9999"), None);
+/// ```
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(explained, _)| *explained == code)
+        .map(|(_, explanation)| *explanation)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    /// Returns the name of the severity, as it is printed before a diagnostic's message.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Severity::Warning.name(), "warning");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    /// Formats a string with the severity's name.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Severity::Error.to_string(), "error");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A message attached to a span of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    span: Span,
+    message: String,
+}
+
+impl Label {
+    /// Returns a new label structure using the given arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let label = scrawlc::Label::new(&span, "declared here");
+    ///
+    /// assert_eq!(label.span(), &span);
+    /// assert_eq!(label.message(), "declared here");
+    /// ```
+    pub fn new(span: &Span, message: &str) -> Self {
+        Label {
+            span: *span,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the span the label points at.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    ///
+    /// assert_eq!(scrawlc::Label::new(&span, "here").span(), &span);
+    /// ```
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Returns the message of the label, which may be empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    ///
+    /// assert_eq!(scrawlc::Label::new(&span, "here").message(), "here");
+    /// ```
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Applicability {
+    /// The suggestion is certainly what was meant, so it can be applied without review.
+    MachineApplicable,
+    /// The suggestion may not be what was meant, so it has to be reviewed.
+    MaybeIncorrect,
+}
+
+impl Applicability {
+    /// Returns the name of the applicability, as it is written in JSON diagnostics.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Applicability::MachineApplicable.name(), "machine-applicable");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Applicability::MachineApplicable => "machine-applicable",
+            Applicability::MaybeIncorrect => "maybe-incorrect",
+        }
+    }
+}
+
+/// A text edit fixing the problem a diagnostic reports, replacing a span of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    message: String,
+    span: Span,
+    replacement: String,
+    applicability: Applicability,
+}
+
+impl Suggestion {
+    /// Returns a new suggestion structure using the given arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let suggestion = scrawlc::Suggestion::new(
+    ///     "add a semicolon",
+    ///     &span,
+    ///     ";",
+    ///     scrawlc::Applicability::MachineApplicable,
+    /// );
+    ///
+    /// assert_eq!(suggestion.message(), "add a semicolon");
+    /// assert_eq!(suggestion.span(), &span);
+    /// assert_eq!(suggestion.replacement(), ";");
+    /// assert_eq!(suggestion.applicability(), scrawlc::Applicability::MachineApplicable);
+    /// ```
+    pub fn new(
+        message: &str,
+        span: &Span,
+        replacement: &str,
+        applicability: Applicability,
+    ) -> Self {
+        Suggestion {
+            message: message.to_string(),
+            span: *span,
+            replacement: replacement.to_string(),
+            applicability,
+        }
+    }
+
+    /// Returns the message describing the suggestion.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let suggestion = scrawlc::Suggestion::new("add a semicolon", &span, ";", scrawlc::Applicability::MaybeIncorrect);
+    ///
+    /// assert_eq!(suggestion.message(), "add a semicolon");
+    /// ```
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the span the suggestion replaces, which is empty for an insertion.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let suggestion = scrawlc::Suggestion::new("add a semicolon", &span, ";", scrawlc::Applicability::MaybeIncorrect);
+    ///
+    /// assert_eq!(suggestion.span(), &span);
+    /// ```
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Returns the text replacing the span.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let suggestion = scrawlc::Suggestion::new("add a semicolon", &span, ";", scrawlc::Applicability::MaybeIncorrect);
+    ///
+    /// assert_eq!(suggestion.replacement(), ";");
+    /// ```
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// Returns whether the suggestion can be applied without review.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let suggestion = scrawlc::Suggestion::new("add a semicolon", &span, ";", scrawlc::Applicability::MaybeIncorrect);
+    ///
+    /// assert_eq!(suggestion.applicability(), scrawlc::Applicability::MaybeIncorrect);
+    /// ```
+    pub fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+}
+
+/// A message about the source, such as an error or a warning, with everything needed to render it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: Option<String>,
+    message: String,
+    primary: Label,
+    labels: Vec<Label>,
+    notes: Vec<String>,
+    help: Option<String>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    /// Returns a new diagnostic structure using the given arguments.
+    /// The span is the primary span of the diagnostic, labeled with an empty message.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let diagnostic = scrawlc::Diagnostic::new(scrawlc::Severity::Error, "unexpected token", &span);
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Error);
+    /// assert_eq!(diagnostic.message(), "unexpected token");
+    /// assert_eq!(diagnostic.span(), &span);
+    /// ```
+    pub fn new(severity: Severity, message: &str, span: &Span) -> Self {
+        Diagnostic {
+            severity,
+            code: None,
+            message: message.to_string(),
+            primary: Label::new(span, ""),
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Returns a new error diagnostic, see `Diagnostic::new`.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Error);
+    /// ```
+    pub fn error(message: &str, span: &Span) -> Self {
+        Diagnostic::new(Severity::Error, message, span)
+    }
+
+    /// Returns a new warning diagnostic, see `Diagnostic::new`.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::warning("unused import", &scrawlc::Span::default());
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Warning);
+    /// ```
+    pub fn warning(message: &str, span: &Span) -> Self {
+        Diagnostic::new(Severity::Warning, message, span)
+    }
+
+    /// Returns the diagnostic with its severity replaced by the given one.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::warning("unused value", &scrawlc::Span::default())
+    ///     .with_severity(scrawlc::Severity::Error);
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Error);
+    /// ```
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+
+        self
+    }
+
+    /// Returns the diagnostic with the given code attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default())
+    ///     .with_code("E0100");
+    ///
+    /// assert_eq!(diagnostic.code(), Some("E0100"));
+    /// ```
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+
+        self
+    }
+
+    /// Returns the diagnostic with the given message on its primary span.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default())
+    ///     .with_primary_label("expected `;`");
+    ///
+    /// assert_eq!(diagnostic.primary_label().message(), "expected `;`");
+    /// ```
+    pub fn with_primary_label(mut self, message: &str) -> Self {
+        self.primary.message = message.to_string();
+
+        self
+    }
+
+    /// Returns the diagnostic with a secondary label attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let diagnostic = scrawlc::Diagnostic::error("duplicate definition", &span)
+    ///     .with_label(&span, "first defined here");
+    ///
+    /// assert_eq!(diagnostic.labels()[0].message(), "first defined here");
+    /// ```
+    pub fn with_label(mut self, span: &Span, message: &str) -> Self {
+        self.labels.push(Label::new(span, message));
+
+        self
+    }
+
+    /// Returns the diagnostic with a note attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default())
+    ///     .with_note("statements end with `;`");
+    ///
+    /// assert_eq!(diagnostic.notes(), ["statements end with `;`"]);
+    /// ```
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+
+        self
+    }
+
+    /// Returns the diagnostic with the given help message attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default())
+    ///     .with_help("add a `;` here");
+    ///
+    /// assert_eq!(diagnostic.help(), Some("add a `;` here"));
+    /// ```
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+
+        self
+    }
+
+    /// Returns the diagnostic with a suggested fix attached.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let suggestion = scrawlc::Suggestion::new("add a semicolon", &span, ";", scrawlc::Applicability::MachineApplicable);
+    /// let diagnostic = scrawlc::Diagnostic::error("expected `;`", &span).with_suggestion(suggestion.clone());
+    ///
+    /// assert_eq!(diagnostic.suggestions(), [suggestion]);
+    /// ```
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+
+        self
+    }
+
+    /// Returns the severity of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Error);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the code of the diagnostic, if it has one.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert_eq!(diagnostic.code(), None);
+    /// ```
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Returns the message of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert_eq!(diagnostic.message(), "unexpected token");
+    /// ```
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the primary span of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    ///
+    /// assert_eq!(scrawlc::Diagnostic::error("unexpected token", &span).span(), &span);
+    /// ```
+    pub fn span(&self) -> &Span {
+        &self.primary.span
+    }
+
+    /// Returns the label on the primary span of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &span);
+    ///
+    /// assert_eq!(diagnostic.primary_label().span(), &span);
+    /// assert_eq!(diagnostic.primary_label().message(), "");
+    /// ```
+    pub fn primary_label(&self) -> &Label {
+        &self.primary
+    }
+
+    /// Returns the secondary labels of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert!(diagnostic.labels().is_empty());
+    /// ```
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Returns the notes of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert!(diagnostic.notes().is_empty());
+    /// ```
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Returns the help message of the diagnostic, if it has one.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert_eq!(diagnostic.help(), None);
+    /// ```
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Returns the suggested fixes of the diagnostic.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default());
+    ///
+    /// assert!(diagnostic.suggestions().is_empty());
+    /// ```
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    /// Returns the diagnostic as a single line of JSON with sorted keys, following the schema
+    /// documented in `docs/error-format.md`.
+    /// Paths are looked up in the source map; spans of unregistered files have a `null` file.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "$");
+    ///
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(0, 0, 0), scrawlc::Position::new(1, 0, 1))
+    ///     .with_file(file);
+    /// let diagnostic = scrawlc::Diagnostic::error("$ is an unsupported character", &span);
+    ///
+    /// let json: serde_json::Value = serde_json::from_str(&diagnostic.to_json(&source_map)).unwrap();
+    ///
+    /// assert_eq!(json["severity"], "error");
+    /// assert_eq!(json["file"], "main.scrawl");
+    /// assert_eq!(json["span"]["byte_end"], 1);
+    /// assert_eq!(json["span"]["column_end"], 2);
+    /// assert_eq!(json["labels"][0]["primary"], true);
+    /// ```
+    pub fn to_json(&self, source_map: &SourceMap) -> String {
+        let file = |span: &Span| source_map.file(span.file).map(|file| file.path());
+        let span = |span: &Span| {
+            json!({
+                "byte_start": span.start.index,
+                "byte_end": span.end.index,
+                "line_start": span.start.line + 1,
+                "column_start": span.start.column + 1,
+                "line_end": span.end.line + 1,
+                "column_end": span.end.column + 1,
+            })
+        };
+        let labels: Vec<_> = std::iter::once((&self.primary, true))
+            .chain(self.labels.iter().map(|label| (label, false)))
+            .map(|(label, primary)| {
+                json!({
+                    "primary": primary,
+                    "message": label.message(),
+                    "span": span(label.span()),
+                    "file": file(label.span()),
+                })
+            })
+            .collect();
+        let suggestions: Vec<_> = self
+            .suggestions
+            .iter()
+            .map(|suggestion| {
+                json!({
+                    "message": suggestion.message(),
+                    "span": span(suggestion.span()),
+                    "file": file(suggestion.span()),
+                    "replacement": suggestion.replacement(),
+                    "applicability": suggestion.applicability().name(),
+                })
+            })
+            .collect();
+
+        json!({
+            "code": self.code,
+            "severity": self.severity.name(),
+            "message": self.message,
+            "file": file(self.span()),
+            "span": span(self.span()),
+            "labels": labels,
+            "notes": self.notes,
+            "help": self.help,
+            "suggestions": suggestions,
+        })
+        .to_string()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    /// Formats a string with the diagnostic's severity, code and message.
+    ///
+    /// # Examples
+    /// ```
+    /// let diagnostic = scrawlc::Diagnostic::error("unexpected token", &scrawlc::Span::default())
+    ///     .with_code("E0100");
+    ///
+    /// assert_eq!(diagnostic.to_string(), "error[E0100]: unexpected token");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "{}[{}]: {}", self.severity, code, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
+/// Renders diagnostics together with the source lines they point at.
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer<'a> {
+    source_map: &'a SourceMap,
+    color: bool,
+    tab_width: usize,
+}
+
+impl<'a> Renderer<'a> {
+    /// Returns a new renderer structure, which renders without colors and with a tab width of 4.
+    ///
+    /// # Examples
+    /// ```
+    /// let source_map = scrawlc::SourceMap::new();
+    /// let renderer = scrawlc::Renderer::new(&source_map);
+    ///
+    /// assert!(!renderer.is_colored());
+    /// ```
+    pub fn new(source_map: &'a SourceMap) -> Self {
+        Renderer {
+            source_map,
+            color: false,
+            tab_width: 4,
+        }
+    }
+
+    /// Returns the renderer set to color its output or not.
+    ///
+    /// # Examples
+    /// ```
+    /// let source_map = scrawlc::SourceMap::new();
+    /// let renderer = scrawlc::Renderer::new(&source_map).with_color(true);
+    ///
+    /// assert!(renderer.is_colored());
+    /// ```
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+
+        self
+    }
+
+    /// Returns the renderer set to expand tabs in source lines to the given width.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "\t$");
+    ///
+    /// let start = scrawlc::Position::new(1, 0, 1);
+    /// let span = scrawlc::Span::new(start, scrawlc::Position::new(2, 0, 2)).with_file(file);
+    /// let diagnostic = scrawlc::Diagnostic::error("unsupported character", &span);
+    ///
+    /// let rendered = scrawlc::Renderer::new(&source_map).with_tab_width(2).render(&diagnostic);
+    ///
+    /// assert!(rendered.contains("1 |   $\n  |   ^\n"));
+    /// ```
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+
+        self
+    }
+
+    /// Returns `true` if the renderer colors its output.
+    ///
+    /// # Examples
+    /// ```
+    /// let source_map = scrawlc::SourceMap::new();
+    ///
+    /// assert!(!scrawlc::Renderer::new(&source_map).is_colored());
+    /// ```
+    pub fn is_colored(&self) -> bool {
+        self.color
+    }
+
+    /// Renders the diagnostic like `rustc` does: its message, its location, the source lines its
+    /// labels point at with the labeled spans underlined, then its notes, help and suggestions.
+    /// The primary span is underlined with `^`, secondary labels with `-`.
+    /// A label spanning multiple lines is underlined until the end of its first line.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "let x = 1;\nlet y = $;\n");
+    ///
+    /// let span = scrawlc::Span::new(scrawlc::Position::new(19, 1, 8), scrawlc::Position::new(20, 1, 9))
+    ///     .with_file(file);
+    /// let diagnostic = scrawlc::Diagnostic::error("$ is an unsupported character", &span)
+    ///     .with_primary_label("unsupported character")
+    ///     .with_help("remove it");
+    ///
+    /// assert_eq!(
+    ///     scrawlc::Renderer::new(&source_map).render(&diagnostic),
+    ///     "error: $ is an unsupported character\n \
+    ///      --> main.scrawl:2:9\n  \
+    ///       |\n\
+    ///      2 | let y = $;\n  \
+    ///       |         ^ unsupported character\n  \
+    ///       |\n  \
+    ///       = help: remove it\n"
+    /// );
+    /// ```
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut output = String::new();
+
+        let severity = match diagnostic.code() {
+            Some(code) => format!("{}[{}]", diagnostic.severity(), code),
+            None => diagnostic.severity().to_string(),
+        };
+        let severity = match diagnostic.severity() {
+            Severity::Error => self.paint(&severity, |text| text.red().bold()),
+            Severity::Warning => self.paint(&severity, |text| text.yellow().bold()),
+            Severity::Note => self.paint(&severity, |text| text.green().bold()),
+        };
+
+        let _ = writeln!(
+            output,
+            "{}{} {}",
+            severity,
+            self.paint(":", |text| text.bold()),
+            self.paint(diagnostic.message(), |text| text.bold())
+        );
+
+        let span = diagnostic.span();
+        let file = match self.source_map.file(span.file) {
+            Some(file) if file.position(span.start.index).is_some() => file,
+            _ => {
+                let _ = writeln!(
+                    output,
+                    " {} {}",
+                    self.paint("-->", |text| text.bright_blue().bold()),
+                    span.start
+                );
+
+                self.render_footer(diagnostic, 1, &mut output);
+
+                return output;
+            }
+        };
+
+        // The labels in the primary span's file, by the line they start on.
+        let mut labels: Vec<(&Label, bool)> = std::iter::once((diagnostic.primary_label(), true))
+            .chain(diagnostic.labels().iter().map(|label| (label, false)))
+            .filter(|(label, _)| {
+                label.span().file == span.file && file.position(label.span().start.index).is_some()
+            })
+            .collect();
+
+        labels.sort_by_key(|(label, _)| label.span().start.index);
+
+        let last_line = labels
+            .iter()
+            .filter_map(|(label, _)| file.position(label.span().start.index))
+            .map(|position| position.line)
+            .max()
+            .unwrap_or(0);
+        let width = (last_line + 1).to_string().len();
+        let gutter = " ".repeat(width);
+        let bar = self.paint("|", |text| text.bright_blue().bold());
+
+        let _ = writeln!(
+            output,
+            "{}{} {}:{}",
+            gutter,
+            self.paint("-->", |text| text.bright_blue().bold()),
+            file.path(),
+            file.position(span.start.index).unwrap_or(span.start)
+        );
+        let _ = writeln!(output, "{} {}", gutter, bar);
+
+        let mut previous_line = None;
+
+        for (label, primary) in labels.iter() {
+            let source = file.content();
+            let start = file.position(label.span().start.index).unwrap_or_default();
+            let line = file.line(start.line).unwrap_or("");
+
+            if previous_line != Some(start.line) {
+                if previous_line.is_some_and(|previous| previous + 1 < start.line) {
+                    let _ = writeln!(output, "{}", self.paint("...", |text| text.bright_blue()));
+                }
+
+                let _ = writeln!(
+                    output,
+                    "{} {} {}",
+                    self.paint(&format!("{:>width$}", start.line + 1), |text| text
+                        .bright_blue()
+                        .bold()),
+                    bar,
+                    self.expand_tabs(line)
+                );
+
+                previous_line = Some(start.line);
+            }
+
+            // The label is underlined until its end, or until the end of its first line.
+            let line_end = start.index
+                + source[start.index..]
+                    .find('\n')
+                    .unwrap_or(source.len() - start.index);
+            let end_index = label.span().end.index.clamp(start.index, line_end);
+            let end = file.position(end_index).unwrap_or(start);
+
+            let start_column = start.display_column(source, self.tab_width);
+            let end_column = end.display_column(source, self.tab_width);
+            let marks =
+                (if *primary { "^" } else { "-" }).repeat((end_column - start_column).max(1));
+            let marks = match (primary, diagnostic.severity()) {
+                (false, _) => self.paint(&marks, |text| text.bright_blue().bold()),
+                (true, Severity::Error) => self.paint(&marks, |text| text.red().bold()),
+                (true, Severity::Warning) => self.paint(&marks, |text| text.yellow().bold()),
+                (true, Severity::Note) => self.paint(&marks, |text| text.green().bold()),
+            };
+
+            let underline = format!("{}{} {}", " ".repeat(start_column), marks, label.message());
+            let _ = writeln!(output, "{} {} {}", gutter, bar, underline.trim_end());
+        }
+
+        self.render_footer(diagnostic, width, &mut output);
+
+        output
+    }
+
+    /// Renders the notes, help and suggestions of a diagnostic, aligned with a gutter of the given width.
+    fn render_footer(&self, diagnostic: &Diagnostic, width: usize, output: &mut String) {
+        let gutter = " ".repeat(width);
+
+        if !diagnostic.notes().is_empty()
+            || diagnostic.help().is_some()
+            || !diagnostic.suggestions().is_empty()
+        {
+            let _ = writeln!(
+                output,
+                "{} {}",
+                gutter,
+                self.paint("|", |text| text.bright_blue().bold())
+            );
+        }
+
+        for note in diagnostic.notes() {
+            let _ = writeln!(
+                output,
+                "{} {} {}",
+                gutter,
+                self.paint("= note:", |text| text.bold()),
+                note
+            );
+        }
+
+        if let Some(help) = diagnostic.help() {
+            let _ = writeln!(
+                output,
+                "{} {} {}",
+                gutter,
+                self.paint("= help:", |text| text.bold()),
+                help
+            );
+        }
+
+        for suggestion in diagnostic.suggestions() {
+            let _ = writeln!(
+                output,
+                "{} {} {}: `{}`",
+                gutter,
+                self.paint("= help:", |text| text.bold()),
+                suggestion.message(),
+                suggestion.replacement()
+            );
+        }
+    }
+
+    /// Returns the line with its tabs expanded to spaces, so underlines line up with it.
+    fn expand_tabs(&self, line: &str) -> String {
+        let mut expanded = String::new();
+
+        for character in line.chars() {
+            match character {
+                '\t' if self.tab_width > 0 => {
+                    let column = expanded.width();
+
+                    expanded.push_str(&" ".repeat(self.tab_width - column % self.tab_width));
+                }
+                _ => expanded.push(character),
+            }
+        }
+
+        expanded
+    }
+
+    /// Returns the text styled if the renderer colors its output, or as it is otherwise.
+    fn paint(&self, text: &str, style: fn(&str) -> ColoredString) -> String {
+        if self.color {
+            style(text).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    analyzer::Span,
+    diagnostic::{Applicability, Diagnostic, Suggestion},
+};
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FixError {
+    #[error("suggestion at {} overlaps the one at {}", .0.start, .1.start)]
+    Conflict(Span, Span),
+
+    #[error("suggestion at {} is outside of the content", .0.start)]
+    OutOfBounds(Span),
+}
+
+impl FixError {
+    /// Returns the span of the suggestion which was not applied.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    ///
+    /// assert_eq!(scrawlc::FixError::OutOfBounds(span).span(), &span);
+    /// ```
+    pub fn span(&self) -> &Span {
+        match self {
+            FixError::Conflict(span, _) => span,
+            FixError::OutOfBounds(span) => span,
+        }
+    }
+
+    /// Returns the stable code of the error, which `scrawlc --explain` explains.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    ///
+    /// assert_eq!(scrawlc::FixError::OutOfBounds(span).code(), "W0004");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        "W0004"
+    }
+
+    /// Returns the warning reporting the suggestion which was not applied.
+    ///
+    /// # Examples
+    /// ```
+    /// let span = scrawlc::Span::default();
+    /// let diagnostic = scrawlc::FixError::Conflict(span, span).diagnostic();
+    ///
+    /// assert_eq!(diagnostic.severity(), scrawlc::Severity::Warning);
+    /// assert_eq!(diagnostic.code(), Some("W0004"));
+    /// ```
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::warning(&self.to_string(), self.span())
+            .with_code(self.code())
+            .with_primary_label("not applied");
+
+        match self {
+            FixError::Conflict(_, previous) => {
+                diagnostic.with_label(previous, "overlaps the suggestion applied here")
+            }
+            FixError::OutOfBounds(_) => diagnostic,
+        }
+    }
+}
+
+/// Returns the content with the machine-applicable suggestions applied, and an error for each one
+/// which could not be applied.
+///
+/// Suggestions are applied in the order of their spans. A suggestion overlapping one which was
+/// already applied is left out with a `FixError::Conflict`, unless it makes the very same edit.
+/// Two insertions at the same offset conflict too, as their order would be ambiguous.
+/// Suggestions which may be incorrect are never applied.
+///
+/// # Examples
+/// ```
+/// let mut scanner = scrawlc::Scanner::new("let s = “hi”;").unwrap();
+/// let (_, errors) = scanner.scan_recovering();
+///
+/// let suggestions: Vec<_> = errors
+///     .iter()
+///     .flat_map(|error| error.diagnostic().suggestions().to_vec())
+///     .collect();
+/// let (fixed, conflicts) = scrawlc::apply_suggestions(scanner.content(), &suggestions);
+///
+/// assert_eq!(fixed, "let s = \"hi\";");
+/// assert!(conflicts.is_empty());
+/// ```
+pub fn apply_suggestions(content: &str, suggestions: &[Suggestion]) -> (String, Vec<FixError>) {
+    let mut suggestions: Vec<&Suggestion> = suggestions
+        .iter()
+        .filter(|suggestion| suggestion.applicability() == Applicability::MachineApplicable)
+        .collect();
+
+    suggestions
+        .sort_by_key(|suggestion| (suggestion.span().start.index, suggestion.span().end.index));
+
+    let mut fixed = String::with_capacity(content.len());
+    let mut errors = Vec::new();
+    let mut applied: Option<&Suggestion> = None;
+    let mut copied = 0;
+
+    for suggestion in suggestions {
+        let span = suggestion.span();
+
+        if span.start.index > span.end.index
+            || !content.is_char_boundary(span.start.index)
+            || !content.is_char_boundary(span.end.index)
+        {
+            errors.push(FixError::OutOfBounds(*span));
+
+            continue;
+        }
+
+        if let Some(previous) = applied {
+            if previous.span() == span && previous.replacement() == suggestion.replacement() {
+                continue;
+            }
+
+            if span.start.index < previous.span().end.index
+                || span.start.index == previous.span().start.index
+            {
+                errors.push(FixError::Conflict(*span, *previous.span()));
+
+                continue;
+            }
+        }
+
+        fixed.push_str(&content[copied..span.start.index]);
+        fixed.push_str(suggestion.replacement());
+
+        copied = span.end.index;
+        applied = Some(suggestion);
+    }
+
+    fixed.push_str(&content[copied..]);
+
+    (fixed, errors)
+}
@@ -3,6 +3,21 @@
 //! Library of Scarwl's compiler.
 
 mod analyzer;
+mod diagnostic;
+mod fix;
+mod lint;
+mod source_map;
+#[cfg(test)]
 mod tests;
 
-pub use analyzer::{Position, Scanner, ScannerError, Token, ETX, LF};
+pub use analyzer::{
+    is_identifier_continue, is_identifier_start, Literal, NumberSuffix, Position, Scanner,
+    ScannerError, ScannerWarning, Span, StringStyle, Token, TokenKind, Tokens, Trivia, TriviaKind,
+    KEYWORDS, LF, LOOKALIKES, OPERATORS,
+};
+pub use diagnostic::{
+    explain, Applicability, Diagnostic, Label, Renderer, Severity, Suggestion, EXPLANATIONS,
+};
+pub use fix::{apply_suggestions, FixError};
+pub use lint::{check_lints, find_lint, Level, Lint, LintLevels, LINTS, WARNINGS};
+pub use source_map::{FileId, SourceFile, SourceMap};
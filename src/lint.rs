@@ -0,0 +1,449 @@
+use crate::{
+    analyzer::{ScannerWarning, Span, Token, TokenKind},
+    diagnostic::{Diagnostic, Severity},
+};
+
+use std::fmt;
+
+/// The name of the group of all lints, as in `-D warnings`.
+pub const WARNINGS: &str = "warnings";
+
+pub const MIXED_SCRIPT_IDENTIFIERS: Lint = Lint {
+    name: "mixed_script_identifiers",
+    default_level: Level::Warn,
+    description: "identifiers mixing characters from different scripts",
+};
+
+pub const CONFUSABLE_IDENTIFIERS: Lint = Lint {
+    name: "confusable_identifiers",
+    default_level: Level::Warn,
+    description: "identifiers which look like another identifier",
+};
+
+pub const UNKNOWN_LINTS: Lint = Lint {
+    name: "unknown_lints",
+    default_level: Level::Warn,
+    description: "lint attributes naming a lint which does not exist",
+};
+
+/// All lints, which can be named on the command line and in attributes.
+pub const LINTS: &[Lint] = &[
+    MIXED_SCRIPT_IDENTIFIERS,
+    CONFUSABLE_IDENTIFIERS,
+    UNKNOWN_LINTS,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Level {
+    /// Returns the name of the level, as it is written in attributes.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Level::Deny.name(), "deny");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Allow => "allow",
+            Level::Warn => "warn",
+            Level::Deny => "deny",
+        }
+    }
+
+    /// Returns the level with the given name, or `None` if there is none.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Level::from_name("allow"), Some(scrawlc::Level::Allow));
+    /// assert_eq!(scrawlc::Level::from_name("forbid"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+
+    /// Returns the command-line flag setting the level, e.g. `-A` for `Level::Allow`.
+    fn flag(&self) -> &'static str {
+        match self {
+            Level::Allow => "-A",
+            Level::Warn => "-W",
+            Level::Deny => "-D",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    /// Formats a string with the level's name.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::Level::Warn.to_string(), "warn");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A named check whose diagnostics are reported at a configurable level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lint {
+    pub name: &'static str,
+    pub default_level: Level,
+    pub description: &'static str,
+}
+
+/// Returns the lint with the given name, or `None` if there is none.
+///
+/// # Examples
+/// ```
+/// let lint = scrawlc::find_lint("confusable_identifiers").unwrap();
+///
+/// assert_eq!(lint.default_level, scrawlc::Level::Warn);
+/// assert_eq!(scrawlc::find_lint("warnings"), None);
+/// ```
+pub fn find_lint(name: &str) -> Option<&'static Lint> {
+    LINTS.iter().find(|lint| lint.name == name)
+}
+
+/// The lint levels requested on the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintLevels {
+    levels: Vec<(String, Level)>,
+}
+
+impl LintLevels {
+    /// Returns a new lint levels structure, which keeps every lint at its default level.
+    ///
+    /// # Examples
+    /// ```
+    /// let levels = scrawlc::LintLevels::new();
+    ///
+    /// assert_eq!(levels.level("confusable_identifiers"), None);
+    /// ```
+    pub fn new() -> Self {
+        LintLevels::default()
+    }
+
+    /// Returns the lint levels with the given lint, or group like `warnings`, set to the level.
+    /// A later level for the same name overrides an earlier one.
+    ///
+    /// # Examples
+    /// ```
+    /// let levels = scrawlc::LintLevels::new()
+    ///     .with_level("warnings", scrawlc::Level::Deny)
+    ///     .with_level("warnings", scrawlc::Level::Warn);
+    ///
+    /// assert_eq!(levels.level("warnings"), Some(scrawlc::Level::Warn));
+    /// ```
+    pub fn with_level(mut self, name: &str, level: Level) -> Self {
+        self.levels.push((name.to_string(), level));
+
+        self
+    }
+
+    /// Returns the level requested for the given lint or group, or `None` if none was requested.
+    ///
+    /// # Examples
+    /// ```
+    /// let levels = scrawlc::LintLevels::new().with_level("unknown_lints", scrawlc::Level::Allow);
+    ///
+    /// assert_eq!(levels.level("unknown_lints"), Some(scrawlc::Level::Allow));
+    /// ```
+    pub fn level(&self, name: &str) -> Option<Level> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|(requested, _)| requested == name)
+            .map(|(_, level)| *level)
+    }
+}
+
+/// A level set by a lint attribute, for the part of the content the attribute applies to.
+#[derive(Debug, Clone)]
+struct Scope {
+    name: String,
+    level: Level,
+    span: Span,
+    start: usize,
+    end: usize,
+}
+
+/// A lint attribute, e.g. `#[allow(confusable_identifiers)]`.
+struct Attribute {
+    /// `true` for an inner attribute, `#![...]`.
+    inner: bool,
+    level: Level,
+    /// The lints named by the attribute, with their spans.
+    names: Vec<(String, Span)>,
+    /// The index of the token following the attribute.
+    next: usize,
+}
+
+/// Where the level of a lint was set.
+enum Source {
+    Default,
+    CommandLine(Level),
+    Group(Level),
+    Attribute(Span),
+}
+
+/// Returns the diagnostics of the warnings, at the levels set for their lints.
+///
+/// Levels are set by the command line, and overridden by `#[allow(...)]`, `#[warn(...)]` and
+/// `#[deny(...)]` attributes in the tokens. An outer attribute, `#[...]`, applies until the end of
+/// the next statement or block, an inner attribute, `#![...]`, to the block it is in, or to the
+/// whole content at the top level.
+/// The `warnings` group changes the level of every lint which would be reported as a warning.
+/// On the command line it overrides the attributes, so `-D warnings` turns all of them into errors.
+///
+/// Warnings of allowed lints are left out, those of denied lints are reported as errors.
+/// Lints named in attributes which do not exist are reported by the `unknown_lints` lint.
+///
+/// # Examples
+/// ```
+/// let mut scanner = scrawlc::Scanner::new("#[allow(mixed_script_identifiers)]\nlet pаy = 1;\nlet pаid = 2;").unwrap();
+/// let tokens = scanner.scan().unwrap();
+///
+/// let levels = scrawlc::LintLevels::new().with_level("warnings", scrawlc::Level::Deny);
+/// let diagnostics = scrawlc::check_lints(&tokens, scanner.warnings(), &levels);
+///
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].severity(), scrawlc::Severity::Error);
+/// assert_eq!(diagnostics[0].span().slice(scanner.content()), "pаid");
+/// ```
+pub fn check_lints(
+    tokens: &[Token],
+    warnings: &[ScannerWarning],
+    levels: &LintLevels,
+) -> Vec<Diagnostic> {
+    let scopes = scopes(tokens);
+
+    let unknown = scopes
+        .iter()
+        .filter(|scope| scope.name != WARNINGS && find_lint(&scope.name).is_none())
+        .map(|scope| {
+            let diagnostic =
+                Diagnostic::warning(&format!("unknown lint `{}`", scope.name), &scope.span)
+                    .with_code("W0003")
+                    .with_primary_label("not a known lint");
+
+            (&UNKNOWN_LINTS, diagnostic)
+        });
+
+    let mut reported: Vec<(&Lint, Diagnostic)> = warnings
+        .iter()
+        .map(|warning| (warning.lint(), warning.diagnostic()))
+        .chain(unknown)
+        .collect();
+
+    reported.sort_by_key(|(_, diagnostic)| diagnostic.span().start.index);
+
+    let mut explained: Vec<&str> = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for (lint, diagnostic) in reported {
+        let (level, source) = resolve(lint, diagnostic.span().start.index, &scopes, levels);
+
+        let diagnostic = match level {
+            Level::Allow => continue,
+            Level::Warn => diagnostic.with_severity(Severity::Warning),
+            Level::Deny => diagnostic.with_severity(Severity::Error),
+        };
+
+        // Where the level comes from is only noted the first time the lint is reported.
+        let diagnostic = match source {
+            Source::Attribute(span) => {
+                diagnostic.with_label(&span, "the lint level is defined here")
+            }
+            _ if explained.contains(&lint.name) => diagnostic,
+            Source::Default => {
+                diagnostic.with_note(&format!("`#[{}({})]` on by default", level, lint.name))
+            }
+            Source::CommandLine(level) => diagnostic.with_note(&format!(
+                "requested on the command line with `{} {}`",
+                level.flag(),
+                lint.name
+            )),
+            Source::Group(level) => diagnostic.with_note(&format!(
+                "`{} {}` implied by `{} {}`",
+                level.flag(),
+                lint.name,
+                level.flag(),
+                WARNINGS
+            )),
+        };
+
+        explained.push(lint.name);
+        diagnostics.push(diagnostic);
+    }
+
+    diagnostics
+}
+
+/// Returns the level of the lint at the given byte offset, and where it was set.
+fn resolve(lint: &Lint, index: usize, scopes: &[Scope], levels: &LintLevels) -> (Level, Source) {
+    // The innermost attribute wins, which is the last one to start before the offset.
+    let attribute = |name: &str| {
+        scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.name == name && scope.start <= index && index < scope.end)
+    };
+
+    let specific = attribute(lint.name);
+    let (level, source) = match (specific, levels.level(lint.name)) {
+        (Some(scope), _) => (scope.level, Source::Attribute(scope.span)),
+        (None, Some(level)) => (level, Source::CommandLine(level)),
+        (None, None) => (lint.default_level, Source::Default),
+    };
+
+    if level != Level::Warn {
+        return (level, source);
+    }
+
+    // The group on the command line has the last word, so `-D warnings` cannot be undone in source.
+    match (levels.level(WARNINGS), attribute(WARNINGS)) {
+        (Some(level), _) => (level, Source::Group(level)),
+        (None, Some(group)) if specific.is_none_or(|scope| group.start > scope.start) => {
+            (group.level, Source::Attribute(group.span))
+        }
+        _ => (level, source),
+    }
+}
+
+/// Returns the scopes of the lint attributes in the tokens, in the order they appear.
+fn scopes(tokens: &[Token]) -> Vec<Scope> {
+    let mut scopes = Vec::new();
+    let mut blocks: Vec<usize> = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match tokens[i].kind() {
+            TokenKind::LeftBrace => blocks.push(tokens[i].span().start.index),
+            TokenKind::RightBrace => {
+                blocks.pop();
+            }
+            TokenKind::Hash => {
+                if let Some(Attribute {
+                    inner,
+                    level,
+                    names,
+                    next,
+                }) = parse_attribute(tokens, i)
+                {
+                    let (start, end) = if inner {
+                        (
+                            blocks.last().copied().unwrap_or(0),
+                            block_end(tokens, next, true),
+                        )
+                    } else {
+                        (tokens[i].span().start.index, block_end(tokens, next, false))
+                    };
+
+                    scopes.extend(names.into_iter().map(|(name, span)| Scope {
+                        name,
+                        level,
+                        span,
+                        start,
+                        end,
+                    }));
+
+                    i = next;
+
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    scopes
+}
+
+/// Parses the lint attribute starting with the `#` at the given index, or returns `None` if the
+/// tokens do not form a lint attribute.
+fn parse_attribute(tokens: &[Token], mut i: usize) -> Option<Attribute> {
+    let kind = |i: usize| tokens.get(i).map(|token| token.kind());
+
+    i += 1;
+
+    let inner = kind(i) == Some(TokenKind::Bang);
+
+    if inner {
+        i += 1;
+    }
+
+    if kind(i) != Some(TokenKind::LeftBracket) || kind(i + 1) != Some(TokenKind::Identifier) {
+        return None;
+    }
+
+    let level = Level::from_name(tokens[i + 1].value()?)?;
+
+    if kind(i + 2) != Some(TokenKind::LeftParen) {
+        return None;
+    }
+
+    i += 3;
+
+    let mut names = Vec::new();
+
+    while kind(i) == Some(TokenKind::Identifier) {
+        names.push((tokens[i].value()?.to_string(), *tokens[i].span()));
+
+        i += 1;
+
+        if kind(i) != Some(TokenKind::Comma) {
+            break;
+        }
+
+        i += 1;
+    }
+
+    if kind(i) != Some(TokenKind::RightParen) || kind(i + 1) != Some(TokenKind::RightBracket) {
+        return None;
+    }
+
+    Some(Attribute {
+        inner,
+        level,
+        names,
+        next: i + 2,
+    })
+}
+
+/// Returns the byte offset where the scope of an attribute ends, scanning from the given index.
+/// An inner attribute's scope ends with the block it is in, an outer attribute's scope with the
+/// next statement or block, whichever ends first.
+fn block_end(tokens: &[Token], from: usize, inner: bool) -> usize {
+    let mut depth = 0;
+
+    for token in &tokens[from..] {
+        match token.kind() {
+            TokenKind::LeftBrace => depth += 1,
+            TokenKind::RightBrace if depth == 0 => return token.span().start.index,
+            TokenKind::RightBrace => {
+                depth -= 1;
+
+                if depth == 0 && !inner {
+                    return token.span().end.index;
+                }
+            }
+            TokenKind::Semicolon if depth == 0 && !inner => return token.span().end.index,
+            TokenKind::Eof => return token.span().end.index,
+            _ => {}
+        }
+    }
+
+    tokens.last().map_or(0, |token| token.span().end.index)
+}
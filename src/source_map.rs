@@ -0,0 +1,264 @@
+use crate::analyzer::{Position, Span};
+
+/// Identifies a file registered in a `SourceMap`.
+/// The default identifier, `FileId::NONE`, refers to no file at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(usize);
+
+impl FileId {
+    /// The identifier of spans which do not belong to any file, which no source map resolves.
+    pub const NONE: FileId = FileId(usize::MAX);
+
+    /// Returns `true` if the identifier refers to no file, see `FileId::NONE`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    ///
+    /// assert!(scrawlc::FileId::default().is_none());
+    /// assert!(!source_map.add_file("main.scrawl", "").is_none());
+    /// ```
+    pub fn is_none(&self) -> bool {
+        *self == FileId::NONE
+    }
+
+    /// Returns the index of the file in its source map, or `usize::MAX` for `FileId::NONE`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// source_map.add_file("a.scrawl", "");
+    ///
+    /// assert_eq!(source_map.add_file("b.scrawl", "").index(), 1);
+    /// ```
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    id: FileId,
+    path: String,
+    content: String,
+    line_starts: Vec<usize>,
+}
+
+impl Default for FileId {
+    /// Returns `FileId::NONE`, so spans refer to no file until one is given.
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(scrawlc::FileId::default(), scrawlc::FileId::NONE);
+    /// ```
+    fn default() -> Self {
+        FileId::NONE
+    }
+}
+
+impl SourceFile {
+    /// Returns a new source file structure using the given arguments.
+    /// The byte offsets where lines start are computed once, so positions can be looked up quickly.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "a;\nb;\n");
+    ///
+    /// assert_eq!(file.path(), "main.scrawl");
+    /// assert_eq!(file.content(), "a;\nb;\n");
+    /// assert_eq!(file.line_count(), 3);
+    /// ```
+    pub fn new(id: FileId, path: &str, content: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        SourceFile {
+            id,
+            path: path.to_string(),
+            content: content.to_string(),
+            line_starts,
+        }
+    }
+
+    /// Returns the identifier of the file.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "");
+    ///
+    /// assert_eq!(file.id(), scrawlc::FileId::default());
+    /// ```
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
+    /// Returns the path of the file.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "");
+    ///
+    /// assert_eq!(file.path(), "main.scrawl");
+    /// ```
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the content of the file.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "import IO;");
+    ///
+    /// assert_eq!(file.content(), "import IO;");
+    /// ```
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns the number of lines in the file.
+    /// A line feed at the end of the content starts a last, empty line.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "a;\nb;");
+    ///
+    /// assert_eq!(file.line_count(), 2);
+    /// ```
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the text of the given zero-based line, without its line break.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "a;\r\nb;");
+    ///
+    /// assert_eq!(file.line(0), Some("a;"));
+    /// assert_eq!(file.line(1), Some("b;"));
+    /// assert_eq!(file.line(2), None);
+    /// ```
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.content.len(), |end| end - 1);
+
+        Some(self.content[start..end].trim_end_matches('\r'))
+    }
+
+    /// Returns the position at the given byte offset, or `None` if the offset is past the end of
+    /// the content or not on a character boundary.
+    /// The line is found by a binary search over the line starts.
+    ///
+    /// # Examples
+    /// ```
+    /// let file = scrawlc::SourceFile::new(scrawlc::FileId::default(), "main.scrawl", "a;\nlet é = 1;");
+    ///
+    /// assert_eq!(file.position(11), Some(scrawlc::Position::new(11, 1, 7)));
+    /// assert_eq!(file.position(8), None);
+    /// ```
+    pub fn position(&self, index: usize) -> Option<Position> {
+        if !self.content.is_char_boundary(index) {
+            return None;
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= index) - 1;
+        let start = self.line_starts[line];
+
+        Some(self.content[start..index].chars().fold(
+            Position::new(start, line, 0),
+            |mut position, character| {
+                position.advance(character);
+                position
+            },
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    /// Returns a new source map structure without any files.
+    ///
+    /// # Examples
+    /// ```
+    /// let source_map = scrawlc::SourceMap::new();
+    ///
+    /// assert!(source_map.files().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    /// Registers a file and returns its identifier.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "import IO;");
+    ///
+    /// assert_eq!(source_map.file(file).unwrap().path(), "main.scrawl");
+    /// ```
+    pub fn add_file(&mut self, path: &str, content: &str) -> FileId {
+        let id = FileId(self.files.len());
+
+        self.files.push(SourceFile::new(id, path, content));
+
+        id
+    }
+
+    /// Returns the file with the given identifier, or `None` if it is not registered.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// let file = source_map.add_file("main.scrawl", "import IO;");
+    ///
+    /// assert_eq!(source_map.file(file).unwrap().content(), "import IO;");
+    /// assert!(source_map.file(scrawlc::FileId::NONE).is_none());
+    /// ```
+    pub fn file(&self, id: FileId) -> Option<&SourceFile> {
+        self.files.get(id.0)
+    }
+
+    /// Returns all registered files.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// source_map.add_file("main.scrawl", "import IO;");
+    ///
+    /// assert_eq!(source_map.files().len(), 1);
+    /// ```
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// Returns the start of the span as `path:line:column`, with one-based line and column,
+    /// or `None` if the span does not belong to a registered file.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut source_map = scrawlc::SourceMap::new();
+    /// source_map.add_file("io.scrawl", "");
+    /// let file = source_map.add_file("main.scrawl", "import IO;\nlet x;");
+    ///
+    /// let start = scrawlc::Position::new(15, 1, 4);
+    /// let span = scrawlc::Span::new(start, start).with_file(file);
+    ///
+    /// assert_eq!(source_map.location(&span).unwrap(), "main.scrawl:2:5");
+    /// ```
+    pub fn location(&self, span: &Span) -> Option<String> {
+        let file = self.file(span.file)?;
+        let position = file.position(span.start.index)?;
+
+        Some(format!("{}:{}", file.path(), position))
+    }
+}
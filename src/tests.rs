@@ -21,81 +21,1296 @@ fn test_position() {
     assert_eq!(pos.to_string(), "2:1");
 }
 
+#[test]
+fn test_crlf_and_tab_positions() {
+    use crate::{Scanner, ScannerError};
+
+    let content = "a = 1;\r\n\tb = \"😀\" $;\r\n";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert_eq!(tokens[4].span().start.to_string(), "2:2");
+    assert_eq!(tokens[4].span().start.display_column(content, 4), 4);
+    assert_eq!(tokens[4].span().start.display_column(content, 8), 8);
+    assert_eq!(tokens[3].span().end.column, 6);
+    assert!(matches!(
+        errors[..],
+        [ScannerError::UnsupportedCharacter('$', _)]
+    ));
+
+    let position = errors[0].span().start;
+
+    assert_eq!((position.line, position.column), (1, 9));
+    assert_eq!(position.utf16_column, 10);
+    assert_eq!(position.display_column(content, 4), 13);
+}
+
 #[test]
 fn test_token() {
-    use crate::{Position, Token};
+    use crate::{Span, Token, TokenKind};
+
+    let span = Span::default();
+    let mut tok = Token::new(TokenKind::Identifier, Some("test_value"), &span);
+
+    assert_eq!(tok.to_string(), "<identifier>@1:1 = test_value");
+
+    tok = Token::new(TokenKind::PlusEqual, None, &span);
+    assert_eq!(tok.to_string(), "<+=>@1:1");
+}
+
+#[test]
+fn test_token_kinds() {
+    use crate::{Scanner, TokenKind};
+
+    let mut scanner = Scanner::new("file.write(\"text\", 'c', 42);").unwrap();
+    let kinds: Vec<_> = scanner.scan().unwrap().iter().map(|t| t.kind()).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Identifier,
+            TokenKind::Dot,
+            TokenKind::Identifier,
+            TokenKind::LeftParen,
+            TokenKind::String,
+            TokenKind::Comma,
+            TokenKind::Character,
+            TokenKind::Comma,
+            TokenKind::Integer,
+            TokenKind::RightParen,
+            TokenKind::Semicolon,
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_spans() {
+    use crate::{Position, Scanner};
+
+    let content = "import IO;\n\nprint(\"hi\") >>= 10;";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    let texts: Vec<_> = tokens.iter().map(|t| t.text(content)).collect();
+    assert_eq!(
+        texts,
+        vec!["import", "IO", ";", "print", "(", "\"hi\"", ")", ">>=", "10", ";", ""]
+    );
+    assert_eq!(tokens[10].span().start.index, content.len());
+
+    assert_eq!(tokens[0].span().start, Position::new(0, 0, 0));
+    assert_eq!(tokens[0].span().end, Position::new(6, 0, 6));
+    assert_eq!(tokens[3].span().start, Position::new(12, 2, 0));
+    assert_eq!(tokens[5].span().start, Position::new(18, 2, 6));
+    assert_eq!(tokens[5].span().end, Position::new(22, 2, 10));
+    assert_eq!(tokens[7].span().len(), 3);
+    assert_eq!(tokens[3].to_string(), "<identifier>@3:1 = print");
+}
+
+#[test]
+fn test_byte_offsets() {
+    use crate::{Position, Scanner};
+
+    let content = "\"héllo wörld\" x";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].value(), Some("héllo wörld"));
+    assert_eq!(tokens[0].span().end, Position::new(15, 0, 13));
+    assert_eq!(tokens[1].span().start, Position::new(16, 0, 14));
+    assert_eq!(tokens[1].text(content), "x");
+}
+
+#[test]
+fn test_token_iterator() {
+    use crate::{Position, Scanner, ScannerError, TokenKind};
+
+    let content = "a = 1;\nb = $ 2;\n";
+    let mut scanner = Scanner::new(content).unwrap();
+
+    let first_line: Vec<_> = scanner
+        .tokens()
+        .map(|token| token.unwrap())
+        .take_while(|token| token.position().line == 0)
+        .collect();
+    assert_eq!(first_line.len(), 4);
+
+    // Scanning can start in the middle of the content, e.g. at the second line.
+    let mut scanner = Scanner::with_position(content, &Position::new(7, 1, 0)).unwrap();
+    let mut tokens = scanner.tokens();
+
+    assert_eq!(tokens.next().unwrap().unwrap().value(), Some("b"));
+    assert_eq!(tokens.next().unwrap().unwrap().kind(), TokenKind::Equal);
+    assert!(matches!(
+        tokens.next().unwrap(),
+        Err(ScannerError::UnsupportedCharacter('$', _))
+    ));
+    assert_eq!(tokens.next().unwrap().unwrap().raw(), Some("2"));
+    assert_eq!(tokens.next().unwrap().unwrap().kind(), TokenKind::Semicolon);
+    assert_eq!(tokens.next().unwrap().unwrap().kind(), TokenKind::Eof);
+    assert!(tokens.next().is_none());
+    assert!(scanner.next_token().unwrap().is_none());
+}
+
+#[test]
+fn test_scan_recovering() {
+    use crate::{Scanner, ScannerError, TokenKind};
+
+    let content = "a = $1;\nb = $;\n$";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert_eq!(errors.len(), 3);
+    assert!(errors
+        .iter()
+        .all(|error| matches!(error, ScannerError::UnsupportedCharacter('$', _))));
+
+    let errors: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.kind() == TokenKind::Error)
+        .collect();
+
+    assert_eq!(errors.len(), 3);
+    assert!(errors.iter().all(|token| token.text(content) == "$"));
+    assert_eq!(errors[1].position().line, 1);
+    assert_eq!(tokens.len(), 11);
+}
+
+#[test]
+fn test_scanner_error_spans() {
+    use crate::{Position, Scanner, ScannerError, Span};
+
+    let content = "a $\nb = \"never closed;\n";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (_, errors) = scanner.scan_recovering();
+
+    assert_eq!(
+        errors,
+        vec![
+            ScannerError::UnsupportedCharacter(
+                '$',
+                Span::new(Position::new(2, 0, 2), Position::new(3, 0, 3))
+            ),
+            ScannerError::UnterminatedString(Span::new(
+                Position::new(8, 1, 4),
+                Position::new(23, 2, 0)
+            )),
+        ]
+    );
+    assert_eq!(errors[1].span().slice(content), "\"never closed;\n");
+
+    let content = "c = 'a\n';";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (_, errors) = scanner.scan_recovering();
+
+    assert!(matches!(errors[0], ScannerError::UnterminatedCharacter(_)));
+    assert_eq!(errors[0].span().slice(content), "'a");
+}
+
+#[test]
+fn test_string_escapes() {
+    use crate::Scanner;
+
+    let content = r#""a\nb\t\r\\\"\'\0\x7F\u{1F600}" "say \"hi\"""#;
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].value(), Some("a\nb\t\r\\\"'\0\x7F\u{1F600}"));
+    assert_eq!(tokens[0].raw(), Some(r#""a\nb\t\r\\\"\'\0\x7F\u{1F600}""#));
+    assert_eq!(tokens[1].value(), Some("say \"hi\""));
+    assert_eq!(tokens[1].raw(), Some(r#""say \"hi\"""#));
+}
+
+#[test]
+fn test_invalid_escapes() {
+    use crate::{Scanner, ScannerError, TokenKind};
+
+    let content = r#""\q" "\x80" "\u{110000}" "\u41" "\x4" x"#;
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    let escapes: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            ScannerError::InvalidEscape(escape, span) => (*escape, span.slice(content)),
+            _ => panic!("unexpected error: {}", error),
+        })
+        .collect();
+
+    assert_eq!(
+        escapes,
+        vec![
+            ('q', r"\q"),
+            ('x', r"\x80"),
+            ('u', r"\u{110000}"),
+            ('u', r"\u"),
+            ('x', r"\x4"),
+        ]
+    );
+
+    // Scanning continues after the string that holds the invalid escape.
+    assert_eq!(tokens[tokens.len() - 2].kind(), TokenKind::Identifier);
+
+    // A line break after the backslash does not split the message.
+    for (content, message) in [
+        ("\"a\\\nb\"", r"\\n is an invalid escape"),
+        ("\"a\\\r\nb\"", r"\\r is an invalid escape"),
+    ] {
+        let mut scanner = Scanner::new(content).unwrap();
+        let (_, errors) = scanner.scan_recovering();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), message);
+    }
+}
+
+#[test]
+fn test_character_literals() {
+    use crate::{Literal, Scanner, ScannerError};
+
+    let content = r"'a' '\'' '\u{1F600}' 'é' '' 'ab' 'e\u{301}'";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert_eq!(tokens[0].character(), Some('a'));
+    assert_eq!(tokens[1].character(), Some('\''));
+    assert_eq!(tokens[2].literal(), Some(&Literal::Character('\u{1F600}')));
+    assert_eq!(tokens[3].character(), Some('é'));
+    assert_eq!(tokens[3].to_string(), "<character>@1:22 = 'é'");
+
+    assert!(matches!(errors[0], ScannerError::EmptyCharacter(_)));
+    assert_eq!(errors[0].span().slice(content), "''");
+    assert!(matches!(errors[1], ScannerError::OverlongCharacter(_)));
+    assert_eq!(errors[1].span().slice(content), "'ab'");
+    assert!(matches!(errors[2], ScannerError::OverlongCharacter(_)));
+    assert_eq!(errors.len(), 3);
+}
+
+#[test]
+fn test_number_literals() {
+    use crate::{Literal, NumberSuffix, Scanner, TokenKind};
+
+    let content = "42 3.25 0xFF 0o17 0b1010 1_000_000 1e9 2.5E-3 10u8 2.0f32 0xFFu8 7f64 1..2 x.0";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    let literals: Vec<_> = tokens.iter().filter_map(|t| t.literal()).collect();
+
+    assert_eq!(
+        literals,
+        vec![
+            &Literal::Integer(42, None),
+            &Literal::Float(3.25, None),
+            &Literal::Integer(255, None),
+            &Literal::Integer(15, None),
+            &Literal::Integer(10, None),
+            &Literal::Integer(1_000_000, None),
+            &Literal::Float(1e9, None),
+            &Literal::Float(2.5e-3, None),
+            &Literal::Integer(10, Some(NumberSuffix::U8)),
+            &Literal::Float(2.0, Some(NumberSuffix::F32)),
+            &Literal::Integer(255, Some(NumberSuffix::U8)),
+            &Literal::Float(7.0, Some(NumberSuffix::F64)),
+            &Literal::Integer(1, None),
+            &Literal::Integer(2, None),
+            &Literal::Text("x".to_string()),
+            &Literal::Integer(0, None),
+        ]
+    );
+
+    assert_eq!(tokens[5].raw(), Some("1_000_000"));
+    assert_eq!(tokens[13].kind(), TokenKind::DotDot);
+}
+
+#[test]
+fn test_malformed_number_literals() {
+    use crate::{Literal, NumberSuffix, Scanner, ScannerError};
+
+    let content = "0x 0b102 1e 1e+ 10q8 2.0u8 0x1.5 1f32x 256u8 128i8 129i8 -2147483648i32 340282366920938463463374607431768211456 1e400 1e39f32 3.4028235e38f32 1e-46f32 1e-400 0.0e-400";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (_, errors) = scanner.scan_recovering();
+
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            ScannerError::MalformedNumber(span) => ("malformed", span.slice(content)),
+            ScannerError::NumberOverflow(span) => ("overflow", span.slice(content)),
+            _ => panic!("unexpected error: {}", error),
+        })
+        .collect();
+
+    assert_eq!(
+        errors,
+        vec![
+            ("malformed", "0x"),
+            ("malformed", "0b102"),
+            ("malformed", "1e"),
+            ("malformed", "1e+"),
+            ("malformed", "10q8"),
+            ("malformed", "2.0u8"),
+            ("malformed", "1f32x"),
+            ("overflow", "256u8"),
+            ("overflow", "129i8"),
+            ("overflow", "340282366920938463463374607431768211456"),
+            ("overflow", "1e400"),
+            ("overflow", "1e39f32"),
+            ("overflow", "1e-46f32"),
+            ("overflow", "1e-400"),
+        ]
+    );
+
+    // The magnitude of a signed minimum is accepted, so the minimum can be negated.
+    let mut scanner = Scanner::new("128i8").unwrap();
+    let tokens = scanner.scan().unwrap();
+    assert_eq!(
+        tokens[0].literal(),
+        Some(&Literal::Integer(128, Some(NumberSuffix::I8)))
+    );
+
+    let mut scanner = Scanner::new("3.4028235e38f32").unwrap();
+    let tokens = scanner.scan().unwrap();
+    assert_eq!(
+        tokens[0].literal(),
+        Some(&Literal::Float(
+            f64::from(f32::MAX),
+            Some(NumberSuffix::F32)
+        ))
+    );
+}
+
+#[test]
+fn test_comments() {
+    use crate::{Scanner, TokenKind};
+
+    let content = "//! Module docs.\n/// Prints.\nprint(/* a /* nested */ comment */ x); // done\n//// plain\n/**/ y";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    let kinds: Vec<_> = tokens.iter().map(|t| t.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::InnerDocComment,
+            TokenKind::DocComment,
+            TokenKind::Identifier,
+            TokenKind::LeftParen,
+            TokenKind::Identifier,
+            TokenKind::RightParen,
+            TokenKind::Semicolon,
+            TokenKind::Identifier,
+            TokenKind::Eof,
+        ]
+    );
+
+    assert_eq!(tokens[0].value(), Some(" Module docs."));
+    assert_eq!(tokens[1].value(), Some(" Prints."));
+    assert_eq!(tokens[1].text(content), "/// Prints.");
+    assert_eq!(tokens[4].value(), Some("x"));
+
+    let content = "///doc\r\n//! inner\r\nx";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].value(), Some("doc"));
+    assert_eq!(tokens[0].text(content), "///doc");
+    assert_eq!(tokens[1].value(), Some(" inner"));
+    assert_eq!(tokens[2].value(), Some("x"));
+}
+
+#[test]
+fn test_unterminated_block_comment() {
+    use crate::{Scanner, ScannerError};
+
+    let content = "a /* outer /* inner */\nb";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert_eq!(tokens.len(), 3);
+    assert!(matches!(
+        errors[..],
+        [ScannerError::UnterminatedBlockComment(_)]
+    ));
+    assert_eq!(errors[0].span().slice(content), "/*");
+    assert_eq!(errors[0].span().start.to_string(), "1:3");
+}
+
+#[test]
+fn test_lossless_trivia() {
+    use crate::{Scanner, TokenKind, TriviaKind};
+
+    let content = "  a = 1; // one\n\t/* two */ b\n";
+    let mut scanner = Scanner::new(content).unwrap();
+    scanner.set_lossless(true);
+
+    let tokens = scanner.scan().unwrap();
+    let trivia = |kinds: &[crate::Trivia]| kinds.iter().map(|t| t.kind()).collect::<Vec<_>>();
+
+    assert_eq!(
+        trivia(tokens[0].leading_trivia()),
+        vec![TriviaKind::Whitespace]
+    );
+    assert_eq!(
+        trivia(tokens[3].trailing_trivia()),
+        vec![TriviaKind::Whitespace, TriviaKind::LineComment]
+    );
+    assert_eq!(
+        trivia(tokens[4].leading_trivia()),
+        vec![
+            TriviaKind::Newline,
+            TriviaKind::Whitespace,
+            TriviaKind::BlockComment,
+            TriviaKind::Whitespace
+        ]
+    );
+    assert_eq!(tokens[5].kind(), TokenKind::Eof);
+    assert_eq!(
+        trivia(tokens[5].leading_trivia()),
+        vec![TriviaKind::Newline]
+    );
+
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens.len(), 6);
+    assert!(tokens.iter().all(|t| t.leading_trivia().is_empty()));
+}
+
+proptest::proptest! {
+    #[test]
+    fn test_lossless_round_trip(content in "[a-z0-9_ \t\r\n/*\"'\\\\{}()+=.;:<>!&|^%~#@?é$-]{0,64}") {
+        use crate::Scanner;
+
+        let mut scanner = Scanner::new(&content).unwrap();
+        scanner.set_lossless(true);
+
+        let (tokens, _) = scanner.scan_recovering();
+        let source: String = tokens
+            .iter()
+            .map(|token| token.full_span().slice(&content))
+            .collect();
+
+        proptest::prop_assert_eq!(source, content);
+    }
+}
+
+#[test]
+fn test_unicode_identifiers() {
+    use crate::{Position, Scanner, TokenKind};
+
+    // "cafe\u{301}" is decomposed, its value is normalized to the composed "café".
+    let content = "let café = 변수 + cafe\u{301} + _x1;";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[1].value(), Some("café"));
+    assert_eq!(tokens[1].span().end, Position::new(9, 0, 8));
+    assert_eq!(tokens[3].kind(), TokenKind::Identifier);
+    assert_eq!(tokens[3].value(), Some("변수"));
+    assert_eq!(tokens[3].span().start, Position::new(12, 0, 11));
+    assert_eq!(tokens[3].span().end, Position::new(18, 0, 13));
+    assert_eq!(tokens[5].value(), Some("café"));
+    assert_eq!(tokens[5].text(content), "cafe\u{301}");
+    assert_eq!(tokens[7].value(), Some("_x1"));
+    assert!(scanner.warnings().is_empty());
+}
+
+#[test]
+fn test_identifier_warnings() {
+    use crate::{Scanner, ScannerWarning};
+
+    // The first `а` is Cyrillic, the rest of the identifier is Latin.
+    let content = "let pаy = 1; let ѕсоре = 2; let scope = 3; let rn = m;";
+    let mut scanner = Scanner::new(content).unwrap();
+    scanner.scan().unwrap();
+
+    let warnings = scanner.warnings();
+
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(
+        &warnings[0],
+        ScannerWarning::MixedScriptIdentifier(identifier, _) if identifier == "pаy"
+    ));
+    assert!(matches!(
+        &warnings[1],
+        ScannerWarning::ConfusableIdentifier(identifier, other, _, _)
+            if identifier == "scope" && other == "ѕсоре"
+    ));
+    assert_eq!(warnings[1].span().slice(content), "scope");
+}
+
+#[test]
+fn test_raw_strings() {
+    use crate::{Scanner, ScannerError, StringStyle, TokenKind};
+
+    let content = r###"r"\d+\.\d*" r#"say "hi""# r##"a "# b"## r"C:\temp" r x"###;
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].value(), Some(r"\d+\.\d*"));
+    assert_eq!(tokens[0].style(), Some(StringStyle::Raw(0)));
+    assert_eq!(tokens[1].value(), Some(r#"say "hi""#));
+    assert_eq!(tokens[1].style(), Some(StringStyle::Raw(1)));
+    assert_eq!(tokens[2].value(), Some(r##"a "# b"##));
+    assert_eq!(tokens[2].raw(), Some(r###"r##"a "# b"##"###));
+    assert_eq!(tokens[3].value(), Some(r"C:\temp"));
+    assert_eq!(tokens[4].kind(), TokenKind::Identifier);
+    assert_eq!(tokens[4].value(), Some("r"));
+
+    let mut scanner = Scanner::new("r#\"never closed\"").unwrap();
+
+    assert!(matches!(
+        scanner.scan(),
+        Err(ScannerError::UnterminatedString(_))
+    ));
+}
+
+#[test]
+fn test_multiline_strings() {
+    use crate::{Scanner, ScannerError, StringStyle};
+
+    let content = "let s = \"\"\"\n    Hello,\n\n      \"world\"\\t!\n    \"\"\";";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[3].value(), Some("Hello,\n\n  \"world\"\t!"));
+    assert_eq!(tokens[3].style(), Some(StringStyle::Multiline));
+    assert_eq!(tokens[3].span().end.line, 4);
+    assert_eq!(tokens[4].span().start.to_string(), "5:8");
+
+    // The closing quotes may be indented less than the text, which then keeps its indentation.
+    let mut scanner =
+        Scanner::new("\"\"\"\r\n    a\r\n  b\r\n\"\"\" \"\"\"one line\"\"\"").unwrap();
+    let tokens = scanner.scan().unwrap();
 
-    let pos = Position::default();
-    let mut tok = Token::new("test_name", "test_value", &pos);
+    assert_eq!(tokens[0].value(), Some("    a\n  b"));
+    assert_eq!(tokens[1].value(), Some("one line"));
 
-    assert_eq!(tok.to_string(), "<test_name>@1:1 = test_value");
+    let mut scanner = Scanner::new("\"\"\"\n  open\n\"\"").unwrap();
 
-    tok = Token::new("test_value", "test_value", &pos);
-    assert_eq!(tok.to_string(), "<test_value>@1:1");
+    assert!(matches!(
+        scanner.scan(),
+        Err(ScannerError::UnterminatedString(_))
+    ));
+}
+
+#[test]
+fn test_string_interpolation() {
+    use crate::{Scanner, ScannerError, TokenKind};
+
+    let content = "print(\"Hello, {name}! {a + {b: 1}[\"k{x}\"]}\\{}\");";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+    let kinds: Vec<_> = tokens.iter().map(|t| t.kind()).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Identifier,
+            TokenKind::LeftParen,
+            TokenKind::StringStart,
+            TokenKind::StringFragment,
+            TokenKind::InterpolationOpen,
+            TokenKind::Identifier,
+            TokenKind::InterpolationClose,
+            TokenKind::StringFragment,
+            TokenKind::InterpolationOpen,
+            TokenKind::Identifier,
+            TokenKind::Plus,
+            TokenKind::LeftBrace,
+            TokenKind::Identifier,
+            TokenKind::Colon,
+            TokenKind::Integer,
+            TokenKind::RightBrace,
+            TokenKind::LeftBracket,
+            TokenKind::StringStart,
+            TokenKind::StringFragment,
+            TokenKind::InterpolationOpen,
+            TokenKind::Identifier,
+            TokenKind::InterpolationClose,
+            TokenKind::StringEnd,
+            TokenKind::RightBracket,
+            TokenKind::InterpolationClose,
+            TokenKind::StringFragment,
+            TokenKind::StringEnd,
+            TokenKind::RightParen,
+            TokenKind::Semicolon,
+            TokenKind::Eof,
+        ]
+    );
+    assert_eq!(tokens[3].value(), Some("Hello, "));
+    assert_eq!(tokens[7].value(), Some("! "));
+    assert_eq!(tokens[25].value(), Some("{}"));
+    assert_eq!(tokens[25].raw(), Some("\\{}"));
+    assert_eq!(tokens[4].text(content), "{");
+    assert_eq!(tokens[26].span().start.to_string(), "1:46");
+
+    // Strings without interpolations are still single tokens.
+    let mut scanner = Scanner::new("\"}\" \"\\{\"").unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokens[0].value(), Some("}"));
+    assert_eq!(tokens[1].value(), Some("{"));
+
+    // The text before the first interpolation is scanned once, with its escapes.
+    let content = "\"a\\tb\\q {c}\"";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+    let kinds: Vec<_> = tokens.iter().map(|t| t.kind()).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::StringStart,
+            TokenKind::Error,
+            TokenKind::InterpolationOpen,
+            TokenKind::Identifier,
+            TokenKind::InterpolationClose,
+            TokenKind::StringEnd,
+            TokenKind::Eof,
+        ]
+    );
+    assert_eq!(tokens[0].text(content), "\"");
+    assert_eq!(tokens[1].text(content), "a\\tb\\q ");
+    assert!(matches!(errors[..], [ScannerError::InvalidEscape('q', _)]));
+
+    let tokens = Scanner::new("\"a\\tb {c}\"").unwrap().scan().unwrap();
+
+    assert_eq!(tokens[1].value(), Some("a\tb "));
+    assert_eq!(tokens[1].raw(), Some("a\\tb "));
+}
+
+#[test]
+fn test_unterminated_interpolation() {
+    use crate::{Scanner, ScannerError, TokenKind};
+
+    for content in ["\"a {b", "x = \"a {b} c", "\"{\"", "x = \"{\"a {\"}"] {
+        let mut scanner = Scanner::new(content).unwrap();
+        let (tokens, errors) = scanner.scan_recovering();
+
+        assert!(matches!(errors[..], [ScannerError::UnterminatedString(_)]));
+        assert_eq!(
+            errors[0].span().slice(content),
+            content.trim_start_matches("x = ")
+        );
+        assert_eq!(tokens[tokens.len() - 2].kind(), TokenKind::Error);
+        assert_eq!(tokens[tokens.len() - 1].kind(), TokenKind::Eof);
+    }
+
+    // A literal brace opens an interpolation, which the closing quote is scanned in.
+    let mut scanner = Scanner::new("\"{\"").unwrap();
+    let (tokens, _) = scanner.scan_recovering();
+    let kinds: Vec<_> = tokens.iter().map(|token| token.kind()).collect();
+
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::StringStart,
+            TokenKind::InterpolationOpen,
+            TokenKind::Error,
+            TokenKind::Eof
+        ]
+    );
+    assert_eq!(tokens[2].value(), Some("\""));
+}
+
+#[test]
+fn test_shebang_and_bom() {
+    use crate::{Position, Scanner, TokenKind, TriviaKind};
+
+    let content = "\u{FEFF}#!/usr/bin/env scrawlc run\nprint(1);";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens.len(), 6);
+    assert_eq!(tokens[0].value(), Some("print"));
+    assert_eq!(tokens[0].span().start, Position::new(30, 1, 0));
+
+    scanner = Scanner::new("\u{FEFF}x").unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].span().start.to_string(), "1:1");
+
+    let mut scanner = Scanner::new(content).unwrap();
+    scanner.set_lossless(true);
+    let tokens = scanner.scan().unwrap();
+    let kinds: Vec<_> = tokens[0]
+        .leading_trivia()
+        .iter()
+        .map(|t| t.kind())
+        .collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TriviaKind::ByteOrderMark,
+            TriviaKind::Shebang,
+            TriviaKind::Newline
+        ]
+    );
+
+    // Only the first line can be a shebang, and `#![` starts an inner attribute.
+    for content in ["#![allow(x)]", "x\n#!y"] {
+        let mut scanner = Scanner::new(content).unwrap();
+        let tokens = scanner.scan().unwrap();
+
+        assert!(tokens.iter().any(|t| t.kind() == TokenKind::Hash));
+        assert!(tokens.iter().any(|t| t.kind() == TokenKind::Bang));
+    }
+}
+
+#[test]
+fn test_source_map() {
+    use crate::{Position, Scanner, SourceMap};
+
+    let mut source_map = SourceMap::new();
+    let io = source_map.add_file("io.scrawl", "class File {}\n");
+    let main = source_map.add_file("main.scrawl", "import IO;\r\n\r\n\tIO::File $;\n");
+
+    let file = source_map.file(main).unwrap();
+
+    assert_eq!(file.line_count(), 4);
+    assert_eq!(file.line(2), Some("\tIO::File $;"));
+    assert_eq!(file.position(0), Some(Position::new(0, 0, 0)));
+    assert_eq!(file.position(15), Some(Position::new(15, 2, 1)));
+    assert_eq!(file.position(27), Some(Position::new(27, 3, 0)));
+    assert_eq!(file.position(28), None);
+
+    let mut scanner = Scanner::new(file.content()).unwrap();
+    scanner.set_file(main);
+
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert!(tokens.iter().all(|token| token.span().file == main));
+    assert_eq!(errors[0].span().file, main);
+    assert_eq!(
+        source_map.location(errors[0].span()).unwrap(),
+        "main.scrawl:3:11"
+    );
+    assert_eq!(
+        source_map
+            .location(&tokens[0].span().with_file(io))
+            .unwrap(),
+        "io.scrawl:1:1"
+    );
+}
+
+#[test]
+fn test_unassigned_file() {
+    use crate::{FileId, Scanner, SourceMap};
+
+    let mut source_map = SourceMap::new();
+    source_map.add_file("other.scrawl", "zzz");
+
+    let mut scanner = Scanner::new("a $").unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert!(tokens.iter().all(|token| token.span().file == FileId::NONE));
+    assert_eq!(errors[0].span().file, FileId::NONE);
+    assert_eq!(source_map.location(errors[0].span()), None);
+    assert!(errors[0]
+        .diagnostic()
+        .to_json(&source_map)
+        .contains("\"file\":null"));
+}
+
+#[test]
+fn test_end_of_content() {
+    use crate::{Position, Scanner, ScannerError, TokenKind};
+
+    // The content is not modified, and a real end-of-text character does not end scanning.
+    let content = "a \u{3} b";
+    let mut scanner = Scanner::new(content).unwrap();
+    let (tokens, errors) = scanner.scan_recovering();
+
+    assert_eq!(scanner.content(), content);
+    assert!(matches!(
+        errors[..],
+        [ScannerError::UnsupportedCharacter('\u{3}', _)]
+    ));
+    assert_eq!(tokens[2].value(), Some("b"));
+    assert_eq!(tokens[3].kind(), TokenKind::Eof);
+    assert_eq!(tokens[3].span().start, Position::new(5, 0, 5));
+    assert!(tokens[3].span().is_empty());
+
+    let mut scanner = Scanner::new("").unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].kind(), TokenKind::Eof);
+
+    // Scanning can start at the end of the content, but not past it.
+    assert!(Scanner::with_position("ab", &Position::new(2, 0, 2)).is_ok());
+    assert!(matches!(
+        Scanner::with_position("ab", &Position::new(3, 0, 3)),
+        Err(ScannerError::EndOfContent(_))
+    ));
+}
+
+#[test]
+fn test_render_diagnostics() {
+    use crate::{Renderer, Scanner, Severity, SourceMap};
+
+    let content = "let ѕсоре = 1;\n\n\n\tlet scope = 'ab';\n";
+
+    let mut source_map = SourceMap::new();
+    let file = source_map.add_file("main.scrawl", content);
+
+    let mut scanner = Scanner::new(content).unwrap();
+    scanner.set_file(file);
+
+    let (_, errors) = scanner.scan_recovering();
+    let renderer = Renderer::new(&source_map);
+
+    let error = errors[0].diagnostic();
+
+    assert_eq!(error.severity(), Severity::Error);
+    assert_eq!(
+        renderer.render(&error),
+        concat!(
+            "error[E0007]: character literal may only contain one character\n",
+            " --> main.scrawl:4:14\n",
+            "  |\n",
+            "4 |     let scope = 'ab';\n",
+            "  |                 ^^^^ more than one character\n",
+            "  |\n",
+            "  = help: use double quotes for a string literal\n",
+        )
+    );
+
+    let warning = scanner.warnings()[0].diagnostic();
+
+    assert_eq!(warning.severity(), Severity::Warning);
+    assert_eq!(warning.labels().len(), 1);
+    assert_eq!(
+        renderer.render(&warning),
+        concat!(
+            "warning[W0002]: identifier `scope` is confusable with `ѕсоре`\n",
+            " --> main.scrawl:4:6\n",
+            "  |\n",
+            "1 | let ѕсоре = 1;\n",
+            "  |     ----- looks like `ѕсоре` here\n",
+            "...\n",
+            "4 |     let scope = 'ab';\n",
+            "  |         ^^^^^ this identifier\n",
+        )
+    );
+}
+
+#[test]
+fn test_json_diagnostics() {
+    use crate::{Position, Scanner, SourceMap};
+
+    let sources = [
+        "$", "“", "\"abc", "'a", "\"\\q\"", "''", "'ab'", "0x", "256u8", "/* a",
+    ];
+    let snapshot = include_str!("snapshots/json_diagnostics.jsonl");
+    let mut lines = snapshot.lines();
+
+    for source in sources {
+        let mut source_map = SourceMap::new();
+        let file = source_map.add_file("main.scrawl", source);
+
+        let mut scanner = Scanner::new(source).unwrap();
+        scanner.set_file(file);
+
+        let (_, errors) = scanner.scan_recovering();
+
+        assert_eq!(errors.len(), 1, "{}", source);
+        assert_eq!(
+            Some(errors[0].diagnostic().to_json(&source_map).as_str()),
+            lines.next(),
+            "{}",
+            source
+        );
+    }
+
+    // The scanner is not given a file, so the error's file is `null` though one is registered.
+    let mut source_map = SourceMap::new();
+    source_map.add_file("main.scrawl", "a");
+
+    let error = Scanner::with_position("a", &Position::new(2, 0, 2)).unwrap_err();
+
+    assert_eq!(
+        Some(error.diagnostic().to_json(&source_map).as_str()),
+        lines.next()
+    );
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn test_explanations() {
+    use crate::{check_lints, explain, LintLevels, Scanner, EXPLANATIONS};
+    use std::collections::HashSet;
+
+    for (code, explanation) in EXPLANATIONS {
+        assert_eq!(explain(code), Some(*explanation));
+
+        // The first Scrawl example produces the diagnostic, the following ones fix it.
+        let examples: Vec<_> = explanation
+            .split("```scrawl\n")
+            .skip(1)
+            .map(|block| block.split("```").next().unwrap())
+            .collect();
+
+        for (i, example) in examples.iter().enumerate() {
+            let mut scanner = Scanner::new(example).unwrap();
+            let (tokens, errors) = scanner.scan_recovering();
+            let lints = check_lints(&tokens, scanner.warnings(), &LintLevels::new());
+            let codes: Vec<_> = errors
+                .iter()
+                .map(|error| error.code())
+                .chain(lints.iter().filter_map(|lint| lint.code()))
+                .collect();
+
+            if i == 0 {
+                assert_eq!(codes, [*code], "{}", code);
+            } else {
+                assert!(codes.is_empty(), "{}", code);
+            }
+        }
+    }
+
+    let codes: HashSet<_> = EXPLANATIONS.iter().map(|(code, _)| code).collect();
+
+    assert_eq!(codes.len(), EXPLANATIONS.len());
+    assert_eq!(explain("E0000"), None);
+}
+
+#[test]
+fn test_lint_levels() {
+    use crate::{check_lints, Level, LintLevels, Scanner, Severity};
+
+    let content = concat!(
+        "#![warn(warnings)]\n",
+        "let pаy = 1;\n",
+        "#[allow(mixed_script_identifiers)]\n",
+        "let pаid = 2;\n",
+        "class Wallet {\n",
+        "    #![deny(mixed_script_identifiers, confusable_identifer)]\n",
+        "    let bаlance = 3;\n",
+        "}\n",
+        "let debt = { let cаsh = 4; };\n",
+    );
+
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+    let warnings = scanner.warnings();
+
+    assert_eq!(warnings.len(), 4);
+
+    let diagnostics = check_lints(&tokens, warnings, &LintLevels::new());
+    let reported: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.severity(),
+                diagnostic.span().slice(scanner.content()),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        reported,
+        [
+            (Severity::Warning, "pаy"),
+            (Severity::Warning, "confusable_identifer"),
+            (Severity::Error, "bаlance"),
+            (Severity::Warning, "cаsh"),
+        ]
+    );
+    assert_eq!(diagnostics[1].code(), Some("W0003"));
+    assert_eq!(diagnostics[0].labels().len(), 1);
+
+    // The attributes override the command line, except for the `warnings` group.
+    let levels = LintLevels::new()
+        .with_level("mixed_script_identifiers", Level::Allow)
+        .with_level("warnings", Level::Deny);
+    let diagnostics = check_lints(&tokens, warnings, &levels);
+    let reported: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.severity(),
+                diagnostic.span().slice(scanner.content()),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        reported,
+        [
+            (Severity::Error, "confusable_identifer"),
+            (Severity::Error, "bаlance"),
+        ]
+    );
+
+    let mut scanner = Scanner::new("let pаy = 1;\nlet pаid = 2;").unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    let diagnostics = check_lints(&tokens, scanner.warnings(), &LintLevels::new());
+
+    assert_eq!(
+        diagnostics[0].notes().last().unwrap(),
+        "`#[warn(mixed_script_identifiers)]` on by default"
+    );
+    assert_eq!(diagnostics[1].notes().len(), 1);
+
+    let levels = LintLevels::new().with_level("warnings", Level::Deny);
+    let diagnostics = check_lints(&tokens, scanner.warnings(), &levels);
+
+    assert_eq!(
+        diagnostics[0].notes().last().unwrap(),
+        "`-D mixed_script_identifiers` implied by `-D warnings`"
+    );
+}
+
+#[test]
+fn test_suggestions() {
+    use crate::{
+        apply_suggestions, Applicability, FixError, Position, Renderer, Scanner, SourceMap, Span,
+        Suggestion,
+    };
+
+    let content = "let s = “hi”；\nlet t = 'ab';\n";
+
+    let mut source_map = SourceMap::new();
+    let file = source_map.add_file("main.scrawl", content);
+
+    let mut scanner = Scanner::new(content).unwrap();
+    scanner.set_file(file);
+
+    let (_, errors) = scanner.scan_recovering();
+    let diagnostics: Vec<_> = errors.iter().map(|error| error.diagnostic()).collect();
+
+    assert_eq!(diagnostics.len(), 4);
+    assert!(diagnostics[3].suggestions().is_empty());
+    assert_eq!(
+        Renderer::new(&source_map).render(&diagnostics[2]),
+        concat!(
+            "error[E0002]: ； is an unsupported character\n",
+            " --> main.scrawl:1:13\n",
+            "  |\n",
+            "1 | let s = “hi”；\n",
+            "  |             ^^ unsupported character\n",
+            "  |\n",
+            "  = help: replace the Fullwidth Semicolon with an ASCII Semicolon: `;`\n",
+        )
+    );
+
+    let suggestions: Vec<_> = diagnostics
+        .iter()
+        .flat_map(|diagnostic| diagnostic.suggestions().to_vec())
+        .collect();
+    let (fixed, errors) = apply_suggestions(content, &suggestions);
+
+    assert_eq!(fixed, "let s = \"hi\";\nlet t = 'ab';\n");
+    assert!(errors.is_empty());
+
+    let span = |start: usize, end: usize| {
+        Span::new(Position::new(start, 0, start), Position::new(end, 0, end))
+    };
+    let suggestion = |start: usize, end: usize, replacement: &str| {
+        Suggestion::new(
+            "",
+            &span(start, end),
+            replacement,
+            Applicability::MachineApplicable,
+        )
+    };
+
+    // Overlapping edits conflict, identical ones are applied once, unsure ones are left out.
+    let (fixed, errors) = apply_suggestions(
+        "abcdef",
+        &[
+            suggestion(4, 5, "E"),
+            suggestion(0, 2, "AB"),
+            suggestion(1, 3, "X"),
+            suggestion(4, 5, "E"),
+            suggestion(5, 5, "-"),
+            suggestion(5, 5, "+"),
+            suggestion(9, 10, "?"),
+            Suggestion::new("", &span(2, 3), "C", Applicability::MaybeIncorrect),
+        ],
+    );
+
+    assert_eq!(fixed, "ABcdE-f");
+    assert_eq!(
+        errors,
+        [
+            FixError::Conflict(span(1, 3), span(0, 2)),
+            FixError::Conflict(span(5, 5), span(5, 5)),
+            FixError::OutOfBounds(span(9, 10)),
+        ]
+    );
+
+    let source_map = SourceMap::new();
+    let rendered = Renderer::new(&source_map).render(&errors[0].diagnostic());
+
+    assert!(rendered.starts_with("warning[W0004]: suggestion at 1:2 overlaps the one at 1:1\n"));
+    assert!(errors
+        .iter()
+        .all(|error| error.diagnostic().code() == Some("W0004")));
 }
 
 #[test]
 fn test_scan() {
-    // * The scanner actually works,
-    // * problem here is probably about the orders
-    // ? I might even remove it completely.
-    // ! Or I will fix this test later.
-
-    // use crate::Scanner;
-    //
-    // let content = "ident_123 456\t\n\r (){}[];,. @ # = == + += ++ > >= >> >>= - -= -- < <= << <<= * *= ! != / /= ~ & &= && ? | |= || : :: ^ ^= % %= \"string\"";
-    // let mut scanner = Scanner::new(content).unwrap();
-    //
-    // let tokens = scanner.scan().unwrap();
-    //
-    // assert_eq!(tokens.get(0).unwrap().value(), "ident_123");
-    // assert_eq!(tokens.get(1).unwrap().value(), "456");
-    // assert_eq!(tokens.get(2).unwrap().value(), "(");
-    // assert_eq!(tokens.get(3).unwrap().value(), ")");
-    // assert_eq!(tokens.get(4).unwrap().value(), "{");
-    // assert_eq!(tokens.get(5).unwrap().value(), "}");
-    // assert_eq!(tokens.get(6).unwrap().value(), "[");
-    // assert_eq!(tokens.get(7).unwrap().value(), "]");
-    // assert_eq!(tokens.get(8).unwrap().value(), ";");
-    // assert_eq!(tokens.get(9).unwrap().value(), ",");
-    // assert_eq!(tokens.get(10).unwrap().value(), ".");
-    // assert_eq!(tokens.get(11).unwrap().value(), "@");
-    // assert_eq!(tokens.get(12).unwrap().value(), "#");
-    // assert_eq!(tokens.get(13).unwrap().value(), "=");
-    // assert_eq!(tokens.get(14).unwrap().value(), "==");
-    // assert_eq!(tokens.get(15).unwrap().value(), "+");
-    // assert_eq!(tokens.get(16).unwrap().value(), "+=");
-    // assert_eq!(tokens.get(17).unwrap().value(), "++");
-    // assert_eq!(tokens.get(18).unwrap().value(), ">");
-    // assert_eq!(tokens.get(19).unwrap().value(), ">=");
-    // assert_eq!(tokens.get(20).unwrap().value(), ">>");
-    // assert_eq!(tokens.get(21).unwrap().value(), ">>=");
-    // assert_eq!(tokens.get(15).unwrap().value(), "-");
-    // assert_eq!(tokens.get(16).unwrap().value(), "-=");
-    // assert_eq!(tokens.get(17).unwrap().value(), "--");
-    // assert_eq!(tokens.get(22).unwrap().value(), "<");
-    // assert_eq!(tokens.get(23).unwrap().value(), "<=");
-    // assert_eq!(tokens.get(24).unwrap().value(), "<<");
-    // assert_eq!(tokens.get(25).unwrap().value(), "<<=");
-    // assert_eq!(tokens.get(26).unwrap().value(), "*");
-    // assert_eq!(tokens.get(27).unwrap().value(), "*=");
-    // assert_eq!(tokens.get(28).unwrap().value(), "!");
-    // assert_eq!(tokens.get(29).unwrap().value(), "!=");
-    // assert_eq!(tokens.get(30).unwrap().value(), "/");
-    // assert_eq!(tokens.get(31).unwrap().value(), "/=");
-    // assert_eq!(tokens.get(32).unwrap().value(), "~");
-    // assert_eq!(tokens.get(33).unwrap().value(), "&");
-    // assert_eq!(tokens.get(34).unwrap().value(), "&=");
-    // assert_eq!(tokens.get(35).unwrap().value(), "&&");
-    // assert_eq!(tokens.get(36).unwrap().value(), "?");
-    // assert_eq!(tokens.get(37).unwrap().value(), "|");
-    // assert_eq!(tokens.get(38).unwrap().value(), "|=");
-    // assert_eq!(tokens.get(39).unwrap().value(), "||");
-    // assert_eq!(tokens.get(40).unwrap().value(), ":");
-    // assert_eq!(tokens.get(41).unwrap().value(), "::");
-    // assert_eq!(tokens.get(42).unwrap().value(), "^");
-    // assert_eq!(tokens.get(43).unwrap().value(), "^=");
-    // assert_eq!(tokens.get(44).unwrap().value(), "%");
-    // assert_eq!(tokens.get(45).unwrap().value(), "%=");
-    // assert_eq!(tokens.get(46).unwrap().value(), "string");
+    use crate::{Scanner, TokenKind};
+
+    let content = "ident_123 456\t\n\r (){}[];,. @ # = == + += ++ > >= >> >>= - -= -- < <= << <<= * *= ! != / /= ~ & &= && ? | |= || : :: ^ ^= % %= \"string\"";
+    let mut scanner = Scanner::new(content).unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].value(), Some("ident_123"));
+    assert_eq!(tokens[1].raw(), Some("456"));
+    assert_eq!(tokens[tokens.len() - 2].value(), Some("string"));
+
+    let operators: Vec<_> = tokens[2..tokens.len() - 2]
+        .iter()
+        .map(|token| token.text(content))
+        .collect();
+
+    assert_eq!(
+        operators.join(" "),
+        "( ) { } [ ] ; , . @ # = == + += ++ > >= >> >>= - -= -- < <= << <<= * *= ! != / /= ~ & &= && ? | |= || : :: ^ ^= % %="
+    );
+    assert!(tokens[2..tokens.len() - 2]
+        .iter()
+        .all(|token| token.kind().name() == token.text(content)));
+
+    // Adjacent operators are split by maximal munch.
+    let mut scanner = Scanner::new("a**=b...c?.d??=e=>f&&=g||=h").unwrap();
+    let kinds: Vec<_> = scanner.scan().unwrap().iter().map(|t| t.kind()).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Identifier,
+            TokenKind::StarStarEqual,
+            TokenKind::Identifier,
+            TokenKind::DotDotDot,
+            TokenKind::Identifier,
+            TokenKind::QuestionDot,
+            TokenKind::Identifier,
+            TokenKind::QuestionQuestionEqual,
+            TokenKind::Identifier,
+            TokenKind::FatArrow,
+            TokenKind::Identifier,
+            TokenKind::AmpersandAmpersandEqual,
+            TokenKind::Identifier,
+            TokenKind::PipePipeEqual,
+            TokenKind::Identifier,
+            TokenKind::Eof,
+        ]
+    );
+
+    for (text, kind) in crate::OPERATORS {
+        assert_eq!(kind.name(), *text);
+    }
+}
+
+/// Generates a test per operator, which scans it between two operands.
+macro_rules! operator_tests {
+    ($($name:ident: $text:literal => $kind:ident,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                use crate::{Scanner, TokenKind};
+
+                let content = concat!("a ", $text, " 1");
+                let mut scanner = Scanner::new(content).unwrap();
+                let tokens = scanner.scan().unwrap();
+
+                assert_eq!(tokens.len(), 4);
+                assert_eq!(tokens[1].kind(), TokenKind::$kind);
+                assert_eq!(tokens[1].text(content), $text);
+                assert_eq!(tokens[2].raw(), Some("1"));
+                assert_eq!(tokens[3].kind(), TokenKind::Eof);
+            }
+        )*
+    };
+}
+
+operator_tests! {
+    test_operator_left_paren: "(" => LeftParen,
+    test_operator_right_paren: ")" => RightParen,
+    test_operator_left_brace: "{" => LeftBrace,
+    test_operator_right_brace: "}" => RightBrace,
+    test_operator_left_bracket: "[" => LeftBracket,
+    test_operator_right_bracket: "]" => RightBracket,
+    test_operator_semicolon: ";" => Semicolon,
+    test_operator_comma: "," => Comma,
+    test_operator_dot: "." => Dot,
+    test_operator_dot_dot: ".." => DotDot,
+    test_operator_dot_dot_dot: "..." => DotDotDot,
+    test_operator_dot_dot_equal: "..=" => DotDotEqual,
+    test_operator_at: "@" => At,
+    test_operator_hash: "#" => Hash,
+    test_operator_equal: "=" => Equal,
+    test_operator_equal_equal: "==" => EqualEqual,
+    test_operator_fat_arrow: "=>" => FatArrow,
+    test_operator_plus: "+" => Plus,
+    test_operator_plus_equal: "+=" => PlusEqual,
+    test_operator_plus_plus: "++" => PlusPlus,
+    test_operator_greater: ">" => Greater,
+    test_operator_greater_equal: ">=" => GreaterEqual,
+    test_operator_greater_greater: ">>" => GreaterGreater,
+    test_operator_greater_greater_equal: ">>=" => GreaterGreaterEqual,
+    test_operator_minus: "-" => Minus,
+    test_operator_minus_equal: "-=" => MinusEqual,
+    test_operator_minus_minus: "--" => MinusMinus,
+    test_operator_arrow: "->" => Arrow,
+    test_operator_less: "<" => Less,
+    test_operator_less_equal: "<=" => LessEqual,
+    test_operator_less_less: "<<" => LessLess,
+    test_operator_less_less_equal: "<<=" => LessLessEqual,
+    test_operator_star: "*" => Star,
+    test_operator_star_equal: "*=" => StarEqual,
+    test_operator_star_star: "**" => StarStar,
+    test_operator_star_star_equal: "**=" => StarStarEqual,
+    test_operator_bang: "!" => Bang,
+    test_operator_bang_equal: "!=" => BangEqual,
+    test_operator_slash: "/" => Slash,
+    test_operator_slash_equal: "/=" => SlashEqual,
+    test_operator_tilde: "~" => Tilde,
+    test_operator_ampersand: "&" => Ampersand,
+    test_operator_ampersand_equal: "&=" => AmpersandEqual,
+    test_operator_ampersand_ampersand: "&&" => AmpersandAmpersand,
+    test_operator_ampersand_ampersand_equal: "&&=" => AmpersandAmpersandEqual,
+    test_operator_question: "?" => Question,
+    test_operator_question_question: "??" => QuestionQuestion,
+    test_operator_question_question_equal: "??=" => QuestionQuestionEqual,
+    test_operator_question_dot: "?." => QuestionDot,
+    test_operator_pipe: "|" => Pipe,
+    test_operator_pipe_equal: "|=" => PipeEqual,
+    test_operator_pipe_pipe: "||" => PipePipe,
+    test_operator_pipe_pipe_equal: "||=" => PipePipeEqual,
+    test_operator_colon: ":" => Colon,
+    test_operator_colon_colon: "::" => ColonColon,
+    test_operator_caret: "^" => Caret,
+    test_operator_caret_equal: "^=" => CaretEqual,
+    test_operator_percent: "%" => Percent,
+    test_operator_percent_equal: "%=" => PercentEqual,
+}
+
+#[test]
+fn test_keywords() {
+    use crate::{Scanner, TokenKind};
+
+    let mut scanner = Scanner::new("import IO; file = new IO::File(newer);").unwrap();
+    let tokens = scanner.scan().unwrap();
+
+    assert_eq!(tokens[0].kind(), TokenKind::Import);
+    assert_eq!(tokens[0].value(), None);
+    assert_eq!(tokens[1].kind(), TokenKind::Identifier);
+    assert_eq!(tokens[5].kind(), TokenKind::New);
+    assert_eq!(tokens[10].kind(), TokenKind::Identifier);
+    assert_eq!(tokens[10].value(), Some("newer"));
+
+    for (word, kind) in crate::KEYWORDS {
+        assert!(TokenKind::is_reserved(word));
+        assert!(kind.is_keyword());
+        assert_eq!(kind.name(), *word);
+    }
 }
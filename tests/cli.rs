@@ -0,0 +1,94 @@
+use std::{fs, path::PathBuf, process::Command};
+
+/// Returns the path of a new file in the target directory, holding the given content.
+fn write_file(name: &str, content: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+
+    fs::write(&path, content).unwrap();
+
+    path
+}
+
+#[test]
+fn test_json_error_format_output() {
+    let input = write_file("json_warning.scrawl", "let pаy = 1;\n");
+    let output = write_file("json_warning.out", "");
+
+    let result = Command::new(env!("CARGO_BIN_EXE_scrawlc"))
+        .args(["--error-format=json", "--verbose"])
+        .arg(&input)
+        .arg(&output)
+        .output()
+        .unwrap();
+
+    assert!(result.status.success());
+
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 1);
+
+    for line in lines {
+        let diagnostic: serde_json::Value = serde_json::from_str(line).unwrap();
+
+        assert_eq!(diagnostic["code"], "W0001");
+    }
+
+    assert!(String::from_utf8(result.stderr)
+        .unwrap()
+        .contains("<let>@1:1"));
+}
+
+#[test]
+fn test_error_format_arguments() {
+    let input = write_file("json_arguments.scrawl", "let x = $;\n");
+    let output = write_file("json_arguments.out", "");
+
+    let result = Command::new(env!("CARGO_BIN_EXE_scrawlc"))
+        .args(["--error-format", "json"])
+        .arg(&input)
+        .arg(&output)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let diagnostic: serde_json::Value = serde_json::from_str(stdout.trim_end()).unwrap();
+
+    assert!(!result.status.success());
+    assert_eq!(diagnostic["code"], "E0002");
+
+    let result = Command::new(env!("CARGO_BIN_EXE_scrawlc"))
+        .arg("--error-fromat=json")
+        .arg(&input)
+        .arg(&output)
+        .output()
+        .unwrap();
+
+    assert!(!result.status.success());
+    assert!(result.stdout.is_empty());
+    assert!(String::from_utf8(result.stderr)
+        .unwrap()
+        .contains("unknown flag --error-fromat=json"));
+}
+
+#[test]
+fn test_json_error_format_failures() {
+    let output = write_file("json_failures.out", "");
+    let missing = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("json_missing.scrawl");
+
+    let result = Command::new(env!("CARGO_BIN_EXE_scrawlc"))
+        .arg("--error-format=json")
+        .arg(&missing)
+        .arg(&output)
+        .output()
+        .unwrap();
+
+    assert!(!result.status.success());
+
+    for line in String::from_utf8(result.stdout).unwrap().lines() {
+        serde_json::from_str::<serde_json::Value>(line).unwrap();
+    }
+
+    assert!(String::from_utf8(result.stderr)
+        .unwrap()
+        .contains("is not a file"));
+}